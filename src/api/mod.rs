@@ -1,10 +1,20 @@
+use chrono::{Duration, NaiveDate};
 use gloo_net::http::Request;
 use serde_json::json;
 use web_sys::window;
 
-use crate::types::{CalendarEvent, DatabaseConfig, NotionDatabase, NotionListResponse, NotionPage, extract_event_from_page};
+use crate::types::{
+    extract_event_from_page, plain_rich_text, properties_request, CalendarEvent, DatabaseConfig,
+    NotionBlock, NotionDatabase, NotionListResponse, NotionPage, NotionUser, PropertyMap,
+};
 
 const NOTION_VERSION: &str = "2022-06-28";
+/// 在 Notion 中搜索时每个数据库最多返回的结果数
+const SEARCH_PAGE_SIZE: u32 = 20;
+/// 按日期范围查询时往前多查的天数
+///
+/// Notion 的日期筛选只比较开始日期，在范围之前开始、持续到范围内的多日事件要靠多查一段才能找到。
+const MULTI_DAY_LOOKBACK_DAYS: i64 = 62;
 
 /// 获取 Notion API 基础 URL
 /// 生产环境使用 Vercel Edge Function 代理解决 CORS
@@ -49,9 +59,54 @@ impl NotionClient {
         self
     }
 
-    /// 获取所有可访问的数据库
+    /// 获取所有共享给集成的数据库（通过 /search 分页获取）
     pub async fn list_databases(&self) -> Result<Vec<NotionDatabase>, String> {
-        let url = format!("{}/databases", self.base_url);
+        let url = format!("{}/search", self.base_url);
+        let mut databases = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut body = json!({
+                "filter": { "property": "object", "value": "database" },
+                "page_size": 100
+            });
+            if let Some(ref c) = cursor {
+                body["start_cursor"] = json!(c);
+            }
+
+            let response = Request::post(&url)
+                .header("Authorization", &format!("Bearer {}", self.api_key))
+                .header("Notion-Version", NOTION_VERSION)
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .map_err(|e| format!("构建请求失败: {:?}", e))?
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {:?}", e))?;
+
+            if !response.ok() {
+                return Err(format!("API 错误: {}", response.status()));
+            }
+
+            let data: NotionListResponse<NotionDatabase> = response
+                .json()
+                .await
+                .map_err(|e| format!("解析失败: {:?}", e))?;
+
+            databases.extend(data.results);
+
+            match data.next_cursor {
+                Some(next) if data.has_more => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(databases)
+    }
+
+    /// 获取单个数据库详情
+    pub async fn get_database(&self, database_id: &str) -> Result<NotionDatabase, String> {
+        let url = format!("{}/databases/{}", self.base_url, database_id);
 
         let response = Request::get(&url)
             .header("Authorization", &format!("Bearer {}", self.api_key))
@@ -62,45 +117,123 @@ impl NotionClient {
             .map_err(|e| format!("请求失败: {:?}", e))?;
 
         if !response.ok() {
-            return Err(format!("API 错误: {}", response.status()));
+            return Err(match response.status() {
+                401 => "API Key 无效".to_string(),
+                404 => "找不到数据库，请确认已在 Notion 中把数据库共享给集成".to_string(),
+                status => format!("API 错误: {}", status),
+            });
         }
 
-        let data: NotionListResponse<NotionDatabase> = response
+        response
             .json()
             .await
-            .map_err(|e| format!("解析失败: {:?}", e))?;
-
-        Ok(data.results)
+            .map_err(|e| format!("解析失败: {:?}", e))
     }
 
-    /// 获取单个数据库详情
-    pub async fn get_database(&self, database_id: &str) -> Result<NotionDatabase, String> {
-        let url = format!("{}/databases/{}", self.base_url, database_id);
+    /// 查询数据库中的页面
+    pub async fn query_database(&self, database_id: &str) -> Result<Vec<NotionPage>, String> {
+        let url = format!("{}/databases/{}/query", self.base_url, database_id);
 
-        let response = Request::get(&url)
+        let body = json!({
+            "page_size": 100
+        });
+
+        let response = Request::post(&url)
             .header("Authorization", &format!("Bearer {}", self.api_key))
             .header("Notion-Version", NOTION_VERSION)
             .header("Content-Type", "application/json")
+            .json(&body)
+            .map_err(|e| format!("构建请求失败: {:?}", e))?
             .send()
             .await
             .map_err(|e| format!("请求失败: {:?}", e))?;
 
         if !response.ok() {
-            return Err(format!("API 错误: {}", response.status()));
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("API 错误 ({}): {}", status, text));
         }
 
-        response
+        let data: NotionListResponse<NotionPage> = response
             .json()
             .await
-            .map_err(|e| format!("解析失败: {:?}", e))
+            .map_err(|e| format!("解析失败: {:?}", e))?;
+
+        Ok(data.results)
     }
 
-    /// 查询数据库中的页面
-    pub async fn query_database(&self, database_id: &str) -> Result<Vec<NotionPage>, String> {
+    /// 查询开始日期落在 [start, end] 内的页面（自动翻页）
+    pub async fn query_database_range(
+        &self,
+        database_id: &str,
+        date_property: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<NotionPage>, String> {
         let url = format!("{}/databases/{}/query", self.base_url, database_id);
+        let mut pages = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut body = json!({
+                "filter": {
+                    "and": [
+                        { "property": date_property, "date": { "on_or_after": start.to_string() } },
+                        { "property": date_property, "date": { "on_or_before": end.to_string() } }
+                    ]
+                },
+                "sorts": [{ "property": date_property, "direction": "ascending" }],
+                "page_size": 100
+            });
+            if let Some(ref c) = cursor {
+                body["start_cursor"] = json!(c);
+            }
+
+            let response = Request::post(&url)
+                .header("Authorization", &format!("Bearer {}", self.api_key))
+                .header("Notion-Version", NOTION_VERSION)
+                .header("Content-Type", "application/json")
+                .json(&body)
+                .map_err(|e| format!("构建请求失败: {:?}", e))?
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {:?}", e))?;
+
+            if !response.ok() {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                return Err(format!("API 错误 ({}): {}", status, text));
+            }
+
+            let data: NotionListResponse<NotionPage> = response
+                .json()
+                .await
+                .map_err(|e| format!("解析失败: {:?}", e))?;
 
+            pages.extend(data.results);
+
+            match data.next_cursor {
+                Some(next) if data.has_more => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(pages)
+    }
+
+    /// 搜索标题包含 `query` 的页面，只取最近的一页结果
+    pub async fn search_database_by_title(
+        &self,
+        database_id: &str,
+        title_property: &str,
+        date_property: &str,
+        query: &str,
+    ) -> Result<Vec<NotionPage>, String> {
+        let url = format!("{}/databases/{}/query", self.base_url, database_id);
         let body = json!({
-            "page_size": 100
+            "filter": { "property": title_property, "title": { "contains": query } },
+            "sorts": [{ "property": date_property, "direction": "descending" }],
+            "page_size": SEARCH_PAGE_SIZE
         });
 
         let response = Request::post(&url)
@@ -127,12 +260,80 @@ impl NotionClient {
         Ok(data.results)
     }
 
+    /// 获取单个页面（包括已归档的页面）
+    pub async fn get_page(&self, page_id: &str) -> Result<NotionPage, String> {
+        let url = format!("{}/pages/{}", self.base_url, page_id);
+
+        let response = Request::get(&url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("Notion-Version", NOTION_VERSION)
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {:?}", e))?;
+
+        if !response.ok() {
+            return Err(format!("API 错误: {}", response.status()));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("解析失败: {:?}", e))
+    }
+
+    /// 工作区中的所有成员（不含机器人），用于填写人员属性
+    pub async fn list_users(&self) -> Result<Vec<NotionUser>, String> {
+        let mut users = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut url = format!("{}/users?page_size=100", self.base_url);
+            if let Some(ref c) = cursor {
+                url = format!("{}&start_cursor={}", url, c);
+            }
+
+            let response = Request::get(&url)
+                .header("Authorization", &format!("Bearer {}", self.api_key))
+                .header("Notion-Version", NOTION_VERSION)
+                .header("Content-Type", "application/json")
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {:?}", e))?;
+
+            if !response.ok() {
+                return Err(match response.status() {
+                    403 => "集成没有读取用户信息的权限".to_string(),
+                    status => format!("API 错误: {}", status),
+                });
+            }
+
+            let data: NotionListResponse<serde_json::Value> = response
+                .json()
+                .await
+                .map_err(|e| format!("解析失败: {:?}", e))?;
+
+            users.extend(
+                data.results
+                    .into_iter()
+                    .filter(|user| user["type"] == "person")
+                    .filter_map(|user| serde_json::from_value(user).ok()),
+            );
+
+            match data.next_cursor {
+                Some(next) if data.has_more => cursor = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(users)
+    }
+
     /// 创建新页面（事件）
     pub async fn create_page(
         &self,
         database_id: &str,
-        title: &str,
-        date: &str,
+        properties: &PropertyMap,
     ) -> Result<NotionPage, String> {
         let url = format!("{}/pages", self.base_url);
 
@@ -140,22 +341,7 @@ impl NotionClient {
             "parent": {
                 "database_id": database_id
             },
-            "properties": {
-                "Name": {
-                    "title": [
-                        {
-                            "text": {
-                                "content": title
-                            }
-                        }
-                    ]
-                },
-                "Date": {
-                    "date": {
-                        "start": date
-                    }
-                }
-            }
+            "properties": properties_request(properties)
         });
 
         let response = Request::post(&url)
@@ -180,36 +366,167 @@ impl NotionClient {
             .map_err(|e| format!("解析失败: {:?}", e))
     }
 
-    /// 更新页面
+    /// 更新页面属性，只写入 `properties` 中给出的属性
     pub async fn update_page(
         &self,
         page_id: &str,
-        title: Option<&str>,
-        date: Option<&str>,
+        properties: &PropertyMap,
     ) -> Result<NotionPage, String> {
         let url = format!("{}/pages/{}", self.base_url, page_id);
 
-        let mut properties = serde_json::Map::new();
+        let body = json!({ "properties": properties_request(properties) });
 
-        if let Some(t) = title {
-            properties.insert(
-                "Name".to_string(),
-                json!({
-                    "title": [{ "text": { "content": t } }]
-                }),
+        let response = Request::patch(&url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("Notion-Version", NOTION_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .map_err(|e| format!("构建请求失败: {:?}", e))?
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {:?}", e))?;
+
+        if !response.ok() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("API 错误 ({}): {}", status, text));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("解析失败: {:?}", e))
+    }
+
+    /// 删除页面（实际是将页面归档）
+    pub async fn delete_page(&self, page_id: &str) -> Result<(), String> {
+        let url = format!("{}/pages/{}", self.base_url, page_id);
+
+        let body = json!({
+            "archived": true
+        });
+
+        let response = Request::patch(&url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("Notion-Version", NOTION_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .map_err(|e| format!("构建请求失败: {:?}", e))?
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {:?}", e))?;
+
+        if !response.ok() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("API 错误 ({}): {}", status, text));
+        }
+
+        Ok(())
+    }
+
+    /// 获取页面正文的所有顶层块
+    pub async fn get_block_children(&self, block_id: &str) -> Result<Vec<NotionBlock>, String> {
+        let mut blocks = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut url = format!(
+                "{}/blocks/{}/children?page_size=100",
+                self.base_url, block_id
             );
+            if let Some(ref c) = cursor {
+                url.push_str(&format!("&start_cursor={}", c));
+            }
+
+            let response = Request::get(&url)
+                .header("Authorization", &format!("Bearer {}", self.api_key))
+                .header("Notion-Version", NOTION_VERSION)
+                .header("Content-Type", "application/json")
+                .send()
+                .await
+                .map_err(|e| format!("请求失败: {:?}", e))?;
+
+            if !response.ok() {
+                return Err(format!("API 错误: {}", response.status()));
+            }
+
+            let data: NotionListResponse<serde_json::Value> = response
+                .json()
+                .await
+                .map_err(|e| format!("解析失败: {:?}", e))?;
+
+            blocks.extend(data.results.iter().filter_map(NotionBlock::from_value));
+
+            match data.next_cursor {
+                Some(next) if data.has_more => cursor = Some(next),
+                _ => break,
+            }
         }
 
-        if let Some(d) = date {
-            properties.insert(
-                "Date".to_string(),
+        Ok(blocks)
+    }
+
+    /// 在页面末尾追加段落，返回新建的块
+    pub async fn append_paragraphs(
+        &self,
+        block_id: &str,
+        paragraphs: &[String],
+    ) -> Result<Vec<NotionBlock>, String> {
+        let url = format!("{}/blocks/{}/children", self.base_url, block_id);
+
+        let children: Vec<serde_json::Value> = paragraphs
+            .iter()
+            .map(|text| {
                 json!({
-                    "date": { "start": d }
-                }),
-            );
+                    "object": "block",
+                    "type": "paragraph",
+                    "paragraph": { "rich_text": plain_rich_text(text) }
+                })
+            })
+            .collect();
+
+        let body = json!({ "children": children });
+
+        let response = Request::patch(&url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("Notion-Version", NOTION_VERSION)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .map_err(|e| format!("构建请求失败: {:?}", e))?
+            .send()
+            .await
+            .map_err(|e| format!("请求失败: {:?}", e))?;
+
+        if !response.ok() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("API 错误 ({}): {}", status, text));
         }
 
-        let body = json!({ "properties": properties });
+        let data: NotionListResponse<serde_json::Value> = response
+            .json()
+            .await
+            .map_err(|e| format!("解析失败: {:?}", e))?;
+
+        Ok(data
+            .results
+            .iter()
+            .filter_map(NotionBlock::from_value)
+            .collect())
+    }
+
+    /// 替换段落块的文字
+    pub async fn update_paragraph(
+        &self,
+        block_id: &str,
+        text: &str,
+    ) -> Result<NotionBlock, String> {
+        let url = format!("{}/blocks/{}", self.base_url, block_id);
+
+        let body = json!({
+            "paragraph": { "rich_text": plain_rich_text(text) }
+        });
 
         let response = Request::patch(&url)
             .header("Authorization", &format!("Bearer {}", self.api_key))
@@ -227,18 +544,20 @@ impl NotionClient {
             return Err(format!("API 错误 ({}): {}", status, text));
         }
 
-        response
+        let value: serde_json::Value = response
             .json()
             .await
-            .map_err(|e| format!("解析失败: {:?}", e))
+            .map_err(|e| format!("解析失败: {:?}", e))?;
+
+        NotionBlock::from_value(&value).ok_or_else(|| "解析失败: 无效的块".to_string())
     }
 
-    /// 删除页面（实际是将页面归档）
-    pub async fn delete_page(&self, page_id: &str) -> Result<(), String> {
+    /// 恢复已归档的页面
+    pub async fn restore_page(&self, page_id: &str) -> Result<NotionPage, String> {
         let url = format!("{}/pages/{}", self.base_url, page_id);
 
         let body = json!({
-            "archived": true
+            "archived": false
         });
 
         let response = Request::patch(&url)
@@ -257,7 +576,10 @@ impl NotionClient {
             return Err(format!("API 错误 ({}): {}", status, text));
         }
 
-        Ok(())
+        response
+            .json()
+            .await
+            .map_err(|e| format!("解析失败: {:?}", e))
     }
 }
 
@@ -275,3 +597,49 @@ pub async fn load_events_from_database(
 
     Ok(events)
 }
+
+/// 加载指定数据库中与日期范围有交集的事件
+///
+/// 开始日期早于范围的事件只能查到 [`MULTI_DAY_LOOKBACK_DAYS`] 天以内开始的。
+pub async fn load_events_in_range(
+    client: &NotionClient,
+    db_config: &DatabaseConfig,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<CalendarEvent>, String> {
+    let pages = client
+        .query_database_range(
+            &db_config.notion_database_id,
+            &db_config.date_property,
+            start - Duration::days(MULTI_DAY_LOOKBACK_DAYS),
+            end,
+        )
+        .await?;
+
+    Ok(pages
+        .iter()
+        .filter_map(|page| extract_event_from_page(page, db_config))
+        .filter(|event| start <= event.end_date.unwrap_or(event.start_date))
+        .collect())
+}
+
+/// 在 Notion 中按标题搜索指定数据库的事件
+pub async fn search_events(
+    client: &NotionClient,
+    db_config: &DatabaseConfig,
+    query: &str,
+) -> Result<Vec<CalendarEvent>, String> {
+    let pages = client
+        .search_database_by_title(
+            &db_config.notion_database_id,
+            &db_config.title_property,
+            &db_config.date_property,
+            query,
+        )
+        .await?;
+
+    Ok(pages
+        .iter()
+        .filter_map(|page| extract_event_from_page(page, db_config))
+        .collect())
+}
@@ -0,0 +1,285 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use web_sys::{Element, HtmlElement};
+use yew::prelude::*;
+
+use crate::components::calendar::{event_badges, event_subtitle};
+use crate::types::CalendarEvent;
+use crate::utils::weekday_name;
+
+/// 滚动到两端时每次多显示的天数
+const PAGE_DAYS: i64 = 30;
+/// 初始时显示在当前日期之前的天数，使向上滚动能触发加载
+const DAYS_BEFORE: i64 = 7;
+/// 距离顶部或底部多少像素时加载下一段
+const LOAD_THRESHOLD: i32 = 200;
+/// 当前日期前后最多显示的天数
+const MAX_DAYS: i64 = 365;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct AgendaViewProps {
+    pub current_date: NaiveDate,
+    pub events: Vec<CalendarEvent>,
+    pub on_event_click: Callback<CalendarEvent>,
+    /// 需要从 Notion 补充加载的日期范围（含两端），加载完成后调用附带的回调
+    pub on_load_range: Callback<(NaiveDate, NaiveDate, Callback<()>)>,
+}
+
+/// 列表中的一段：有事件的一天，或连续几天都没有事件
+enum AgendaSection<'a> {
+    Day(NaiveDate, Vec<&'a CalendarEvent>),
+    Empty(NaiveDate, NaiveDate),
+}
+
+fn build_sections(
+    events: &[CalendarEvent],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<AgendaSection<'_>> {
+    // 先挑出与显示范围有交集的事件，逐天筛选时不用每次遍历全部已加载的事件
+    let events: Vec<&CalendarEvent> = events
+        .iter()
+        .filter(|e| e.start_date <= end && start <= e.end_date.unwrap_or(e.start_date))
+        .collect();
+    let mut sections = Vec::new();
+    let mut date = start;
+    while date <= end {
+        let mut day_events: Vec<&CalendarEvent> = events
+            .iter()
+            .copied()
+            .filter(|e| e.start_date <= date && date <= e.end_date.unwrap_or(e.start_date))
+            .collect();
+        // 全天事件在前，其余按开始时间
+        day_events.sort_by(|a, b| {
+            (
+                a.start_date == date && a.start_time.is_some(),
+                a.start_time,
+                &a.title,
+            )
+                .cmp(&(
+                    b.start_date == date && b.start_time.is_some(),
+                    b.start_time,
+                    &b.title,
+                ))
+        });
+
+        match (day_events.is_empty(), sections.last_mut()) {
+            (true, Some(AgendaSection::Empty(_, last))) => *last = date,
+            (true, _) => sections.push(AgendaSection::Empty(date, date)),
+            (false, _) => sections.push(AgendaSection::Day(date, day_events)),
+        }
+        date += Duration::days(1);
+    }
+    sections
+}
+
+/// 事件在某一天显示的时间
+fn time_label(event: &CalendarEvent, date: NaiveDate) -> String {
+    match event.start_time {
+        Some(time) if event.start_date == date => time.format("%H:%M").to_string(),
+        _ if event.start_date < date => "继续".to_string(),
+        _ => "全天".to_string(),
+    }
+}
+
+/// 日程视图：按天列出事件，滚动到两端时继续加载
+#[function_component(AgendaView)]
+pub fn agenda_view(props: &AgendaViewProps) -> Html {
+    // 显示范围放在 ref 中，连续的滚动事件不会重复扩展同一段
+    let range = use_mut_ref(|| (props.current_date, props.current_date));
+    let force_update = use_force_update();
+    let list_ref = use_node_ref();
+    let anchor_ref = use_node_ref();
+    // 渲染后需要滚动到当前日期
+    let scroll_to_anchor = use_mut_ref(|| false);
+    // 向前加载前的 (滚动高度, 滚动位置)，渲染后保持原来看到的内容不动
+    let prepend_anchor = use_mut_ref(|| None::<(i32, i32)>);
+    // (向前, 向后) 是否有还没加载完的一段，加载完之前不再继续扩展
+    let loading = use_mut_ref(|| (false, false));
+
+    // 当前日期变化时重新开始
+    {
+        let range = range.clone();
+        let loading = loading.clone();
+        let scroll_to_anchor = scroll_to_anchor.clone();
+        let force_update = force_update.clone();
+        let on_load_range = props.on_load_range.clone();
+        use_effect_with(props.current_date, move |date: &NaiveDate| {
+            let (start, end) = (
+                *date - Duration::days(DAYS_BEFORE),
+                *date + Duration::days(PAGE_DAYS),
+            );
+            *range.borrow_mut() = (start, end);
+            *loading.borrow_mut() = (false, false);
+            *scroll_to_anchor.borrow_mut() = true;
+            on_load_range.emit((start, end, Callback::noop()));
+            force_update.force_update();
+            || ()
+        });
+    }
+
+    // 每次渲染后调整滚动位置
+    {
+        let list_ref = list_ref.clone();
+        let anchor_ref = anchor_ref.clone();
+        let scroll_to_anchor = scroll_to_anchor.clone();
+        let prepend_anchor = prepend_anchor.clone();
+        use_effect(move || {
+            if let Some(list) = list_ref.cast::<Element>() {
+                if let Some((height, top)) = prepend_anchor.borrow_mut().take() {
+                    list.set_scroll_top(list.scroll_height() - height + top);
+                } else if *scroll_to_anchor.borrow() {
+                    // 列表是定位容器，offset_top 就是锚点在列表内的位置
+                    if let Some(anchor) = anchor_ref.cast::<HtmlElement>() {
+                        list.set_scroll_top(anchor.offset_top());
+                        *scroll_to_anchor.borrow_mut() = false;
+                    }
+                }
+            }
+            || ()
+        });
+    }
+
+    let first_day = props.current_date - Duration::days(MAX_DAYS);
+    let last_day = props.current_date + Duration::days(MAX_DAYS);
+
+    let on_scroll = {
+        let range = range.clone();
+        let loading = loading.clone();
+        let list_ref = list_ref.clone();
+        let prepend_anchor = prepend_anchor.clone();
+        let force_update = force_update.clone();
+        let on_load_range = props.on_load_range.clone();
+        Callback::from(move |_: Event| {
+            let Some(list) = list_ref.cast::<Element>() else {
+                return;
+            };
+            let (start, end) = *range.borrow();
+            let (loading_before, loading_after) = *loading.borrow();
+            if list.scroll_top() + list.client_height() >= list.scroll_height() - LOAD_THRESHOLD {
+                if loading_after || end >= last_day {
+                    return;
+                }
+                let new_end = (end + Duration::days(PAGE_DAYS)).min(last_day);
+                *range.borrow_mut() = (start, new_end);
+                loading.borrow_mut().1 = true;
+                let done = {
+                    let loading = loading.clone();
+                    Callback::from(move |()| loading.borrow_mut().1 = false)
+                };
+                on_load_range.emit((end + Duration::days(1), new_end, done));
+                force_update.force_update();
+            } else if list.scroll_top() <= LOAD_THRESHOLD && prepend_anchor.borrow().is_none() {
+                if loading_before || start <= first_day {
+                    return;
+                }
+                let new_start = (start - Duration::days(PAGE_DAYS)).max(first_day);
+                *range.borrow_mut() = (new_start, end);
+                loading.borrow_mut().0 = true;
+                *prepend_anchor.borrow_mut() = Some((list.scroll_height(), list.scroll_top()));
+                let done = {
+                    let loading = loading.clone();
+                    Callback::from(move |()| loading.borrow_mut().0 = false)
+                };
+                on_load_range.emit((new_start, start - Duration::days(1), done));
+                force_update.force_update();
+            }
+        })
+    };
+
+    let (start, end) = *range.borrow();
+    let today = chrono::Local::now().naive_local().date();
+    let sections = build_sections(&props.events, start, end);
+    // 当前日期所在的一段作为滚动锚点
+    let anchor_index = sections.iter().position(|section| match section {
+        AgendaSection::Day(date, _) => *date >= props.current_date,
+        AgendaSection::Empty(_, last) => *last >= props.current_date,
+    });
+
+    let sections = sections
+        .into_iter()
+        .enumerate()
+        .map(|(index, section)| {
+            let node_ref = if Some(index) == anchor_index {
+                anchor_ref.clone()
+            } else {
+                NodeRef::default()
+            };
+            match section {
+                AgendaSection::Empty(first, last) => html! {
+                    <div class="agenda-empty" ref={node_ref}>
+                        {if first == last {
+                            format!("{}月{}日 没有安排", first.month(), first.day())
+                        } else {
+                            format!(
+                                "{}月{}日 - {}月{}日 没有安排",
+                                first.month(), first.day(), last.month(), last.day()
+                            )
+                        }}
+                    </div>
+                },
+                AgendaSection::Day(date, day_events) => html! {
+                    <div
+                        class={classes!("agenda-day", (date == today).then_some("today"))}
+                        ref={node_ref}
+                    >
+                        <div class="agenda-date">
+                            <span class="agenda-day-number">{date.day()}</span>
+                            <span class="agenda-day-meta">
+                                {format!("{}月 {}", date.month(), weekday_name(date.weekday()))}
+                            </span>
+                        </div>
+                        <div class="agenda-events">
+                            {day_events.into_iter().map(|event| {
+                                let on_click = {
+                                    let on_event_click = props.on_event_click.clone();
+                                    let event = event.clone();
+                                    Callback::from(move |_: MouseEvent| on_event_click.emit(event.clone()))
+                                };
+                                html! {
+                                    <div
+                                        class="agenda-event"
+                                        title={event.tooltip()}
+                                        tabindex="0"
+                                        data-event-id={event.id.clone()}
+                                        onclick={on_click}
+                                    >
+                                        <span class="agenda-time">{time_label(event, date)}</span>
+                                        <span
+                                            class="event-color-dot"
+                                            style={format!("background-color: {}", event.color)}
+                                        />
+                                        <div class="agenda-event-body">
+                                            <div class="event-title">
+                                                {&event.title}
+                                                {event_badges(event)}
+                                            </div>
+                                            {event_subtitle(event)}
+                                        </div>
+                                    </div>
+                                }
+                            }).collect::<Html>()}
+                        </div>
+                    </div>
+                },
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="agenda-view">
+            <div class="calendar-header">
+                <h2>{"日程"}</h2>
+            </div>
+            <div class="agenda-list" ref={list_ref} onscroll={on_scroll}>
+                {sections}
+                <div class="agenda-more">
+                    {if end >= last_day {
+                        format!("只显示到 {}", last_day)
+                    } else {
+                        "继续滚动加载更多".to_string()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
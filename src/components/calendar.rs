@@ -1,7 +1,17 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, Duration, NaiveDate};
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, HtmlInputElement};
 use yew::prelude::*;
 
-use crate::types::{CalendarEvent, ViewMode};
+use crate::components::agenda::AgendaView;
+use crate::components::timeline::TimelineView;
+use crate::components::year::YearView;
+use crate::types::{
+    group_events, groupable_properties, CalendarEvent, DatabaseConfig, DisplaySlot, EventGroup,
+    GroupBy, ViewMode, WeekNumbering, WeekStart,
+};
+use crate::utils::date_grid::MonthGrid;
+use crate::utils::navigation::{week_number, week_start};
 use crate::utils::{month_name, weekday_name};
 
 #[derive(Properties, Clone, PartialEq)]
@@ -9,138 +19,537 @@ pub struct CalendarProps {
     pub current_date: NaiveDate,
     pub view_mode: ViewMode,
     pub events: Vec<CalendarEvent>,
+    pub databases: Vec<DatabaseConfig>,
     pub on_date_click: Callback<NaiveDate>,
     pub on_event_click: Callback<CalendarEvent>,
+    /// 切换到指定视图和日期，例如从年视图放大到某个月
+    pub on_navigate: Callback<(ViewMode, NaiveDate)>,
+    /// 拖拽改期或调整结束日期后的事件
+    pub on_event_change: Callback<CalendarEvent>,
+    /// 需要从 Notion 补充加载的日期范围，加载完成后调用附带的回调
+    pub on_load_range: Callback<(NaiveDate, NaiveDate, Callback<()>)>,
+    /// 周视图和日视图的分列方式
+    pub resource_columns: Option<GroupBy>,
+    pub on_resource_columns_change: Callback<Option<GroupBy>>,
+    pub week_start: WeekStart,
+    /// 月视图和周视图左侧的周数，None 时不显示
+    pub week_numbers: Option<WeekNumbering>,
 }
 
 #[function_component(Calendar)]
 pub fn calendar(props: &CalendarProps) -> Html {
     match props.view_mode {
+        ViewMode::Year => html! {
+            <YearView
+                current_date={props.current_date}
+                events={props.events.clone()}
+                databases={props.databases.clone()}
+                on_month_click={props.on_navigate.reform(|date| (ViewMode::Month, date))}
+                on_load_range={props.on_load_range.clone()}
+                week_start={props.week_start}
+            />
+        },
         ViewMode::Month => html! {
             <MonthView
                 current_date={props.current_date}
                 events={props.events.clone()}
+                week_start={props.week_start}
+                week_numbers={props.week_numbers}
                 on_date_click={props.on_date_click.clone()}
                 on_event_click={props.on_event_click.clone()}
+                on_event_change={props.on_event_change.clone()}
             />
         },
         ViewMode::Week => html! {
             <WeekView
                 current_date={props.current_date}
                 events={props.events.clone()}
+                week_start={props.week_start}
+                week_numbers={props.week_numbers}
+                databases={props.databases.clone()}
+                resource_columns={props.resource_columns.clone()}
+                on_resource_columns_change={props.on_resource_columns_change.clone()}
                 on_date_click={props.on_date_click.clone()}
                 on_event_click={props.on_event_click.clone()}
+                on_event_change={props.on_event_change.clone()}
             />
         },
         ViewMode::Day => html! {
             <DayView
                 current_date={props.current_date}
                 events={props.events.clone()}
+                databases={props.databases.clone()}
+                resource_columns={props.resource_columns.clone()}
+                on_resource_columns_change={props.on_resource_columns_change.clone()}
                 on_date_click={props.on_date_click.clone()}
                 on_event_click={props.on_event_click.clone()}
+                on_event_change={props.on_event_change.clone()}
+            />
+        },
+        ViewMode::Agenda => html! {
+            <AgendaView
+                current_date={props.current_date}
+                events={props.events.clone()}
+                on_event_click={props.on_event_click.clone()}
+                on_load_range={props.on_load_range.clone()}
+            />
+        },
+        ViewMode::Timeline => html! {
+            <TimelineView
+                current_date={props.current_date}
+                events={props.events.clone()}
+                databases={props.databases.clone()}
+                on_event_click={props.on_event_click.clone()}
+                on_event_change={props.on_event_change.clone()}
+                on_load_range={props.on_load_range.clone()}
+                week_start={props.week_start}
             />
         },
     }
 }
 
+// ========== 拖拽 ==========
+/// 拖拽中的事件
+#[derive(Clone, PartialEq)]
+struct DragState {
+    event: CalendarEvent,
+    mode: DragMode,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DragMode {
+    /// 整体移动，`anchor` 为按下时所在的日期
+    Move { anchor: NaiveDate },
+    /// 拖动结束日期
+    Resize,
+}
+
+impl DragState {
+    /// 放到 `target` 后的事件；日期没有变化时返回 None
+    fn drop_on(&self, target: NaiveDate) -> Option<CalendarEvent> {
+        let event = &self.event;
+        let (start_date, end_date) = match self.mode {
+            DragMode::Move { anchor } => {
+                let delta = target - anchor;
+                (event.start_date + delta, event.end_date.map(|d| d + delta))
+            }
+            DragMode::Resize => {
+                let end = target.max(event.start_date);
+                (event.start_date, (end != event.start_date).then_some(end))
+            }
+        };
+
+        if start_date == event.start_date && end_date == event.end_date {
+            return None;
+        }
+
+        Some(CalendarEvent {
+            start_date,
+            end_date,
+            ..event.clone()
+        })
+    }
+}
+
+/// `mode` 在拖拽开始时根据鼠标位置决定拖拽方式
+fn on_drag_start(
+    drag: &UseStateHandle<Option<DragState>>,
+    event: &CalendarEvent,
+    mode: impl Fn(&DragEvent) -> DragMode + 'static,
+) -> Callback<DragEvent> {
+    let drag = drag.clone();
+    let event = event.clone();
+    Callback::from(move |e: DragEvent| {
+        // 调整手柄位于事件内部，不能让外层再当作移动处理
+        e.stop_propagation();
+        // Firefox 需要设置数据才会开始拖拽
+        if let Some(data) = e.data_transfer() {
+            let _ = data.set_data("text/plain", &event.title);
+        }
+        drag.set(Some(DragState {
+            event: event.clone(),
+            mode: mode(&e),
+        }));
+    })
+}
+
+fn on_drag_over(
+    drag: &UseStateHandle<Option<DragState>>,
+    drop_target: &UseStateHandle<Option<NaiveDate>>,
+    date: NaiveDate,
+) -> Callback<DragEvent> {
+    let drag = drag.clone();
+    let drop_target = drop_target.clone();
+    Callback::from(move |e: DragEvent| {
+        if drag.is_none() {
+            return;
+        }
+        e.prevent_default();
+        if *drop_target != Some(date) {
+            drop_target.set(Some(date));
+        }
+    })
+}
+
+fn on_drop(
+    drag: &UseStateHandle<Option<DragState>>,
+    drop_target: &UseStateHandle<Option<NaiveDate>>,
+    date: NaiveDate,
+    on_event_change: &Callback<CalendarEvent>,
+) -> Callback<DragEvent> {
+    let drag = drag.clone();
+    let drop_target = drop_target.clone();
+    let on_event_change = on_event_change.clone();
+    Callback::from(move |e: DragEvent| {
+        e.prevent_default();
+        if let Some(event) = drag.as_ref().and_then(|d| d.drop_on(date)) {
+            on_event_change.emit(event);
+        }
+        drag.set(None);
+        drop_target.set(None);
+    })
+}
+
+fn on_drag_end(
+    drag: &UseStateHandle<Option<DragState>>,
+    drop_target: &UseStateHandle<Option<NaiveDate>>,
+) -> Callback<DragEvent> {
+    let drag = drag.clone();
+    let drop_target = drop_target.clone();
+    Callback::from(move |_: DragEvent| {
+        drag.set(None);
+        drop_target.set(None);
+    })
+}
+
+/// 只有已经写入 Notion 的事件才能拖拽
+pub(crate) fn is_draggable(event: &CalendarEvent) -> bool {
+    event.notion_page_id.is_some()
+}
+
+// ========== 额外属性 ==========
+/// 标题旁的徽标
+pub(crate) fn event_badges(event: &CalendarEvent) -> Html {
+    event
+        .slot_texts(DisplaySlot::Badge)
+        .into_iter()
+        .map(|text| html! { <span class="event-badge">{text}</span> })
+        .collect()
+}
+
+/// 标题下方的副标题，多个属性用 · 连接
+pub(crate) fn event_subtitle(event: &CalendarEvent) -> Html {
+    let texts = event.slot_texts(DisplaySlot::Subtitle);
+    if texts.is_empty() {
+        return html! {};
+    }
+    html! { <div class="event-subtitle">{texts.join(" · ")}</div> }
+}
+
 // ========== 月视图 ==========
+/// 每个日期格最多显示的行数，其余收进 "+N"
+const MAX_VISIBLE_LANES: usize = 3;
+
+/// 已分配行的事件，日期已裁剪到可见范围内
+pub(crate) struct LaidOutEvent<'a> {
+    pub(crate) event: &'a CalendarEvent,
+    pub(crate) start: NaiveDate,
+    pub(crate) end: NaiveDate,
+    pub(crate) lane: usize,
+}
+
+/// 为可见范围内的事件分配行
+///
+/// 按开始日期、跨度从长到短排序后贪心放入最靠上的空闲行，所以同一事件
+/// 在它跨过的每一天、每一周都处于同一行，且结果不依赖事件的加载顺序。
+pub(crate) fn assign_lanes(
+    events: &[CalendarEvent],
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Vec<LaidOutEvent<'_>> {
+    let mut visible: Vec<LaidOutEvent> = events
+        .iter()
+        .filter_map(|event| {
+            let end = event
+                .end_date
+                .unwrap_or(event.start_date)
+                .max(event.start_date);
+            if event.start_date > range_end || end < range_start {
+                return None;
+            }
+            Some(LaidOutEvent {
+                event,
+                start: event.start_date.max(range_start),
+                end: end.min(range_end),
+                lane: 0,
+            })
+        })
+        .collect();
+
+    visible.sort_by(|a, b| {
+        a.start
+            .cmp(&b.start)
+            .then(b.end.cmp(&a.end))
+            .then_with(|| a.event.title.cmp(&b.event.title))
+            .then_with(|| a.event.id.cmp(&b.event.id))
+    });
+
+    // 每行最后被占用的日期
+    let mut lane_ends: Vec<NaiveDate> = Vec::new();
+    for item in &mut visible {
+        item.lane = match lane_ends.iter().position(|end| *end < item.start) {
+            Some(lane) => lane,
+            None => {
+                lane_ends.push(item.end);
+                lane_ends.len() - 1
+            }
+        };
+        lane_ends[item.lane] = item.end;
+    }
+
+    visible
+}
+
+/// 一行左侧的周数，不显示周数时为空
+fn week_number_gutter(row_start: NaiveDate, numbering: Option<WeekNumbering>) -> Html {
+    let Some(numbering) = numbering else {
+        return html! {};
+    };
+    let number = week_number(row_start, numbering);
+    html! {
+        <div
+            class="week-number-gutter"
+            title={format!("第 {} 周（{}）", number, numbering.as_str())}
+        >
+            {number}
+        </div>
+    }
+}
+
 #[derive(Properties, Clone, PartialEq)]
 struct MonthViewProps {
     current_date: NaiveDate,
     events: Vec<CalendarEvent>,
+    week_start: WeekStart,
+    week_numbers: Option<WeekNumbering>,
     on_date_click: Callback<NaiveDate>,
     on_event_click: Callback<CalendarEvent>,
+    on_event_change: Callback<CalendarEvent>,
 }
 
 #[function_component(MonthView)]
 fn month_view(props: &MonthViewProps) -> Html {
-    let year = props.current_date.year();
-    let month = props.current_date.month();
-
-    // 计算当月第一天是星期几
-    let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
-    let first_weekday = first_day.weekday();
-
-    // 计算需要显示的上月天数
-    let days_from_prev_month = match first_weekday {
-        Weekday::Mon => 0,
-        Weekday::Tue => 1,
-        Weekday::Wed => 2,
-        Weekday::Thu => 3,
-        Weekday::Fri => 4,
-        Weekday::Sat => 5,
-        Weekday::Sun => 6,
-    };
+    let drag = use_state(|| None::<DragState>);
+    let drop_target = use_state(|| None::<NaiveDate>);
+    let expanded_day = use_state(|| None::<NaiveDate>);
 
-    // 计算当月天数
-    let days_in_month = if month == 12 {
-        NaiveDate::from_ymd_opt(year + 1, 1, 1)
-    } else {
-        NaiveDate::from_ymd_opt(year, month + 1, 1)
-    }
-    .unwrap()
-    .signed_duration_since(first_day)
-    .num_days() as usize;
+    let grid = MonthGrid::for_date(props.current_date, props.week_start.weekday());
+    let grid_start = grid.start;
+    let grid_end = grid.end();
+    let today = chrono::Local::now().naive_local().date();
+    let laid_out = assign_lanes(&props.events, grid_start, grid_end);
 
-    // 生周日历格子
-    let total_cells = days_from_prev_month + days_in_month;
-    let rows = (total_cells + 6) / 7;
-    let total_cells = rows * 7;
+    let weeks: Vec<Html> = grid
+        .week_starts()
+        .map(|week_start| {
+            let week_end = week_start + Duration::days(6);
 
-    let cells: Vec<Html> = (0..total_cells)
-        .map(|i| {
-            let day_offset = i as i64 - days_from_prev_month as i64;
-            let date = first_day + chrono::Duration::days(day_offset);
-            let is_current_month = date.month() == month;
-            let is_today = date == chrono::Local::now().naive_local().date();
+            let cells: Vec<Html> = (0..7)
+                .map(|i| {
+                    let date = week_start + Duration::days(i);
+                    let is_current_month = grid.in_month(date);
 
-            // 获取当天的事件
-            let day_events: Vec<&CalendarEvent> = props
-                .events
-                .iter()
-                .filter(|e| {
-                    date >= e.start_date
-                        && date
-                            <= e.end_date.unwrap_or(e.start_date)
+                    // 当天的事件，按行排序
+                    let mut day_events: Vec<&LaidOutEvent> = laid_out
+                        .iter()
+                        .filter(|e| e.start <= date && date <= e.end)
+                        .collect();
+                    day_events.sort_by_key(|e| e.lane);
+                    let hidden = day_events
+                        .iter()
+                        .filter(|e| e.lane >= MAX_VISIBLE_LANES)
+                        .count();
+
+                    let on_click = {
+                        let on_date_click = props.on_date_click.clone();
+                        Callback::from(move |_| on_date_click.emit(date))
+                    };
+
+                    let on_show_more = {
+                        let expanded_day = expanded_day.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.stop_propagation();
+                            expanded_day.set(Some(date));
+                        })
+                    };
+
+                    let on_close_popover = {
+                        let expanded_day = expanded_day.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.stop_propagation();
+                            expanded_day.set(None);
+                        })
+                    };
+
+                    html! {
+                        <div
+                            class={classes!(
+                                "calendar-day",
+                                (!is_current_month).then_some("other-month"),
+                                (date == today).then_some("today"),
+                                (*drop_target == Some(date)).then_some("drop-target")
+                            )}
+                            tabindex="0"
+                            data-date={date.to_string()}
+                            onclick={on_click}
+                            ondragover={on_drag_over(&drag, &drop_target, date)}
+                            ondrop={on_drop(&drag, &drop_target, date, &props.on_event_change)}
+                        >
+                            <span class="day-number">{date.day()}</span>
+                            {if hidden > 0 {
+                                html! {
+                                    <button class="more-events-btn" onclick={on_show_more}>
+                                        {format!("+{} 更多", hidden)}
+                                    </button>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                            {if *expanded_day == Some(date) {
+                                html! {
+                                    <div class="day-popover" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                                        <div class="day-popover-header">
+                                            <span>{format!("{}月{}日", date.month(), date.day())}</span>
+                                            <button class="close-btn" onclick={on_close_popover}>{"✕"}</button>
+                                        </div>
+                                        {day_events.iter().map(|item| {
+                                            let on_event_click = {
+                                                let on_event_click = props.on_event_click.clone();
+                                                let expanded_day = expanded_day.clone();
+                                                let event = item.event.clone();
+                                                Callback::from(move |_: MouseEvent| {
+                                                    expanded_day.set(None);
+                                                    on_event_click.emit(event.clone())
+                                                })
+                                            };
+                                            html! {
+                                                <div
+                                                    class="event-chip"
+                                                    style={format!("background-color: {}", item.event.color)}
+                                                    title={item.event.tooltip()}
+                                                    tabindex="0"
+                                                    data-event-id={item.event.id.clone()}
+                                                    onclick={on_event_click}
+                                                >
+                                                    {&item.event.title}
+                                                    {event_badges(item.event)}
+                                                </div>
+                                            }
+                                        }).collect::<Html>()}
+                                    </div>
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </div>
+                    }
                 })
                 .collect();
 
-            let on_click = {
-                let on_date_click = props.on_date_click.clone();
-                let date = date;
-                Callback::from(move |_| on_date_click.emit(date))
-            };
+            // 跨天事件在本周内画成一整条，跨周时在周边界断开
+            let bars: Vec<Html> = laid_out
+                .iter()
+                .filter(|e| e.lane < MAX_VISIBLE_LANES && e.start <= week_end && e.end >= week_start)
+                .map(|item| {
+                    let event = item.event;
+                    let segment_start = item.start.max(week_start);
+                    let segment_end = item.end.min(week_end);
+                    let column = (segment_start - week_start).num_days() + 1;
+                    let span = (segment_end - segment_start).num_days() + 1;
+                    let continues_left = event.start_date < segment_start;
+                    let continues_right = event.end_date.unwrap_or(event.start_date) > segment_end;
+
+                    let on_event_click = {
+                        let on_event_click = props.on_event_click.clone();
+                        let event = event.clone();
+                        Callback::from(move |e: MouseEvent| {
+                            e.stop_propagation();
+                            on_event_click.emit(event.clone())
+                        })
+                    };
+
+                    // 按下位置对应的日期作为移动的锚点
+                    let grab_mode = move |e: &DragEvent| {
+                        let width = e
+                            .current_target()
+                            .and_then(|t| t.dyn_into::<HtmlElement>().ok())
+                            .map(|el| el.offset_width())
+                            .unwrap_or(0);
+                        let index = if width > 0 {
+                            (e.offset_x() as i64 * span / width as i64).clamp(0, span - 1)
+                        } else {
+                            0
+                        };
+                        DragMode::Move {
+                            anchor: segment_start + Duration::days(index),
+                        }
+                    };
+
+                    let draggable = is_draggable(event);
+                    html! {
+                        <div
+                            class={classes!(
+                                "event-chip",
+                                "event-bar",
+                                continues_left.then_some("continues-left"),
+                                continues_right.then_some("continues-right")
+                            )}
+                            style={format!(
+                                "background-color: {}; grid-column: {} / span {}; grid-row: {};",
+                                event.color,
+                                column,
+                                span,
+                                item.lane + 1
+                            )}
+                            title={event.tooltip()}
+                            tabindex="0"
+                            data-event-id={event.id.clone()}
+                            onclick={on_event_click}
+                            draggable={draggable.to_string()}
+                            ondragstart={on_drag_start(&drag, event, grab_mode)}
+                            ondragend={on_drag_end(&drag, &drop_target)}
+                        >
+                            {&event.title}
+                            {event_badges(event)}
+                            {event_subtitle(event)}
+                            {if draggable && !continues_right {
+                                html! {
+                                    <span
+                                        class="resize-handle"
+                                        draggable="true"
+                                        title="拖动调整结束日期"
+                                        ondragstart={on_drag_start(&drag, event, |_| DragMode::Resize)}
+                                        ondragend={on_drag_end(&drag, &drop_target)}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </div>
+                    }
+                })
+                .collect();
 
             html! {
-                <div
-                    class={classes!(
-                        "calendar-day",
-                        (!is_current_month).then_some("other-month"),
-                        is_today.then_some("today")
-                    )}
-                    onclick={on_click}
-                >
-                    <span class="day-number">{date.day()}</span>
-                    <div class="day-events">
-                        {day_events.iter().map(|event| {
-                            let on_event_click = {
-                                let on_event_click = props.on_event_click.clone();
-                                let event = (*event).clone();
-                                Callback::from(move |e: MouseEvent| {
-                                    e.stop_propagation();
-                                    on_event_click.emit(event.clone())
-                                })
-                            };
-                            html! {
-                                <div
-                                    class="event-chip"
-                                    style={format!("background-color: {}", event.color)}
-                                    onclick={on_event_click}
-                                >
-                                    {&event.title}
-                                </div>
-                            }
-                        }).collect::<Html>()}
+                <div class="month-week-row">
+                    {week_number_gutter(week_start, props.week_numbers)}
+                    <div class="month-week">
+                        <div class="month-week-days">
+                            {cells}
+                        </div>
+                        <div class="month-week-bars">
+                            {bars}
+                        </div>
                     </div>
                 </div>
             }
@@ -150,16 +559,21 @@ fn month_view(props: &MonthViewProps) -> Html {
     html! {
         <div class="month-view">
             <div class="calendar-header">
-                <h2>{format!("{} {}", year, month_name(month))}</h2>
+                <h2>{format!("{} {}", grid.year, month_name(grid.month))}</h2>
             </div>
-            <div class="weekdays">
-                {["周一", "周二", "周三", "周四", "周五", "周六", "周日"]
+            <div class={classes!("weekdays", props.week_numbers.is_some().then_some("with-week-numbers"))}>
+                {if props.week_numbers.is_some() {
+                    html! { <div class="week-number-gutter" /> }
+                } else {
+                    html! {}
+                }}
+                {props.week_start.weekdays()
                     .iter()
-                    .map(|d| html! { <div class="weekday">{d}</div> })
+                    .map(|d| html! { <div class="weekday">{weekday_name(*d)}</div> })
                     .collect::<Html>()}
             </div>
-            <div class="calendar-grid">
-                {cells}
+            <div class={classes!("calendar-grid", drag.is_some().then_some("dragging"))}>
+                {weeks}
             </div>
         </div>
     }
@@ -170,25 +584,22 @@ fn month_view(props: &MonthViewProps) -> Html {
 struct WeekViewProps {
     current_date: NaiveDate,
     events: Vec<CalendarEvent>,
+    week_start: WeekStart,
+    week_numbers: Option<WeekNumbering>,
+    databases: Vec<DatabaseConfig>,
+    resource_columns: Option<GroupBy>,
+    on_resource_columns_change: Callback<Option<GroupBy>>,
     on_date_click: Callback<NaiveDate>,
     on_event_click: Callback<CalendarEvent>,
+    on_event_change: Callback<CalendarEvent>,
 }
 
 #[function_component(WeekView)]
 fn week_view(props: &WeekViewProps) -> Html {
-    // 计算本周开始（周一）
-    let weekday = props.current_date.weekday();
-    let days_from_monday = match weekday {
-        Weekday::Mon => 0,
-        Weekday::Tue => 1,
-        Weekday::Wed => 2,
-        Weekday::Thu => 3,
-        Weekday::Fri => 4,
-        Weekday::Sat => 5,
-        Weekday::Sun => 6,
-    };
+    let drag = use_state(|| None::<DragState>);
+    let drop_target = use_state(|| None::<NaiveDate>);
 
-    let week_start = props.current_date - chrono::Duration::days(days_from_monday);
+    let week_start = week_start(props.current_date, props.week_start.weekday());
 
     let days: Vec<Html> = (0..7)
         .map(|i| {
@@ -206,14 +617,21 @@ fn week_view(props: &WeekViewProps) -> Html {
 
             let on_click = {
                 let on_date_click = props.on_date_click.clone();
-                let date = date;
                 Callback::from(move |_| on_date_click.emit(date))
             };
 
             html! {
                 <div
-                    class={classes!("week-day", is_today.then_some("today"))}
+                    class={classes!(
+                        "week-day",
+                        is_today.then_some("today"),
+                        (*drop_target == Some(date)).then_some("drop-target")
+                    )}
+                    tabindex="0"
+                    data-date={date.to_string()}
                     onclick={on_click}
+                    ondragover={on_drag_over(&drag, &drop_target, date)}
+                    ondrop={on_drop(&drag, &drop_target, date, &props.on_event_change)}
                 >
                     <div class="week-day-header">
                         <span class="week-day-name">{weekday_name(date.weekday())}</span>
@@ -229,13 +647,35 @@ fn week_view(props: &WeekViewProps) -> Html {
                                     on_event_click.emit(event.clone())
                                 })
                             };
+                            let draggable = is_draggable(event);
+                            let is_last_day = date == event.end_date.unwrap_or(event.start_date);
                             html! {
                                 <div
                                     class="week-event-card"
                                     style={format!("border-left-color: {}", event.color)}
+                                    title={event.tooltip()}
+                                    tabindex="0"
+                                    data-event-id={event.id.clone()}
                                     onclick={on_event_click}
+                                    draggable={draggable.to_string()}
+                                    ondragstart={on_drag_start(&drag, event, move |_| DragMode::Move { anchor: date })}
+                                    ondragend={on_drag_end(&drag, &drop_target)}
                                 >
-                                    <div class="event-title">{&event.title}</div>
+                                    <div class="event-title">{&event.title}{event_badges(event)}</div>
+                                    {event_subtitle(event)}
+                                    {if draggable && is_last_day {
+                                        html! {
+                                            <span
+                                                class="resize-handle"
+                                                draggable="true"
+                                                title="拖动调整结束日期"
+                                                ondragstart={on_drag_start(&drag, event, |_| DragMode::Resize)}
+                                                ondragend={on_drag_end(&drag, &drop_target)}
+                                            />
+                                        }
+                                    } else {
+                                        html! {}
+                                    }}
                                 </div>
                             }
                         }).collect::<Html>()}
@@ -257,48 +697,90 @@ fn week_view(props: &WeekViewProps) -> Html {
                         (week_start + chrono::Duration::days(6)).day()
                     )
                 }</h2>
+                {resource_select(&props.resource_columns, &props.events, &props.on_resource_columns_change)}
             </div>
-            <div class="week-grid">
-                {days}
-            </div>
+            {match &props.resource_columns {
+                Some(group_by) => {
+                    let week_end = week_start + Duration::days(6);
+                    let week_events: Vec<CalendarEvent> = props
+                        .events
+                        .iter()
+                        .filter(|e| {
+                            e.start_date <= week_end && week_start <= e.end_date.unwrap_or(e.start_date)
+                        })
+                        .cloned()
+                        .collect();
+                    html! {
+                        <ResourceGrid
+                            days={(0..7).map(|i| week_start + Duration::days(i)).collect::<Vec<_>>()}
+                            groups={group_events(&week_events, &props.databases, group_by)}
+                            on_date_click={props.on_date_click.clone()}
+                            on_event_click={props.on_event_click.clone()}
+                            on_event_change={props.on_event_change.clone()}
+                        />
+                    }
+                }
+                None => html! {
+                    <div class={classes!("week-grid", props.week_numbers.is_some().then_some("with-week-numbers"))}>
+                        {week_number_gutter(week_start, props.week_numbers)}
+                        {days}
+                    </div>
+                },
+            }}
         </div>
     }
 }
 
 // ========== 日视图 ==========
+/// 日期加上可选的时间
+fn format_date_time(date: NaiveDate, time: Option<chrono::NaiveTime>) -> String {
+    match time {
+        Some(time) => format!(
+            "{} {}",
+            crate::utils::format_date(&date),
+            time.format("%H:%M")
+        ),
+        None => crate::utils::format_date(&date),
+    }
+}
+
 #[derive(Properties, Clone, PartialEq)]
 struct DayViewProps {
     current_date: NaiveDate,
     events: Vec<CalendarEvent>,
+    databases: Vec<DatabaseConfig>,
+    resource_columns: Option<GroupBy>,
+    on_resource_columns_change: Callback<Option<GroupBy>>,
     on_date_click: Callback<NaiveDate>,
     on_event_click: Callback<CalendarEvent>,
+    on_event_change: Callback<CalendarEvent>,
 }
 
 #[function_component(DayView)]
 fn day_view(props: &DayViewProps) -> Html {
     let is_today = props.current_date == chrono::Local::now().naive_local().date();
 
-    let day_events: Vec<&CalendarEvent> = props
+    let day_events: Vec<CalendarEvent> = props
         .events
         .iter()
         .filter(|e| {
             props.current_date >= e.start_date
                 && props.current_date <= e.end_date.unwrap_or(e.start_date)
         })
+        .cloned()
         .collect();
 
-    html! {
-        <div class="day-view">
-            <div class="calendar-header">
-                <h2 class={classes!(is_today.then_some("today-header"))}>
-                    {format!(
-                        "{} {} {}日",
-                        props.current_date.year(),
-                        month_name(props.current_date.month()),
-                        props.current_date.day()
-                    )}
-                </h2>
-            </div>
+    let content = match &props.resource_columns {
+        Some(group_by) => html! {
+            <ResourceGrid
+                days={vec![props.current_date]}
+                groups={group_events(&day_events, &props.databases, group_by)}
+                on_date_click={props.on_date_click.clone()}
+                on_event_click={props.on_event_click.clone()}
+                on_event_change={props.on_event_change.clone()}
+            />
+        },
+        None => html! {
             <div class="day-content">
                 <div class="day-sidebar">
                     <div class="day-info">
@@ -319,22 +801,29 @@ fn day_view(props: &DayViewProps) -> Html {
                             {day_events.iter().map(|event| {
                                 let on_event_click = {
                                     let on_event_click = props.on_event_click.clone();
-                                    let event = (*event).clone();
+                                    let event = event.clone();
                                     Callback::from(move |_| on_event_click.emit(event.clone()))
                                 };
                                 html! {
                                     <div
                                         class="day-event-item"
                                         style={format!("border-left-color: {}", event.color)}
+                                        title={event.tooltip()}
+                                        tabindex="0"
+                                        data-event-id={event.id.clone()}
                                         onclick={on_event_click}
                                     >
                                         <div class="event-color-dot" style={format!("background-color: {}", event.color)}></div>
                                         <div class="event-details">
-                                            <div class="event-title-large">{&event.title}</div>
+                                            <div class="event-title-large">
+                                                {&event.title}
+                                                {event_badges(event)}
+                                            </div>
+                                            {event_subtitle(event)}
                                             <div class="event-date">
                                                 {format!("{} - {}",
-                                                    crate::utils::format_date(&event.start_date),
-                                                    event.end_date.map(|d| crate::utils::format_date(&d))
+                                                    format_date_time(event.start_date, event.start_time),
+                                                    event.end_date.map(|d| format_date_time(d, event.end_time))
                                                         .unwrap_or_else(|| "无结束日期".to_string())
                                                 )}
                                             </div>
@@ -346,6 +835,304 @@ fn day_view(props: &DayViewProps) -> Html {
                     }}
                 </div>
             </div>
+        },
+    };
+
+    html! {
+        <div class="day-view">
+            <div class="calendar-header">
+                <h2 class={classes!(is_today.then_some("today-header"))}>
+                    {format!(
+                        "{} {} {}日",
+                        props.current_date.year(),
+                        month_name(props.current_date.month()),
+                        props.current_date.day()
+                    )}
+                </h2>
+                {resource_select(&props.resource_columns, &props.events, &props.on_resource_columns_change)}
+            </div>
+            {content}
         </div>
     }
 }
+
+// ========== 分列 ==========
+/// 分列方式在下拉框中的值
+fn group_by_value(group_by: &Option<GroupBy>) -> String {
+    match group_by {
+        None => String::new(),
+        Some(GroupBy::Database) => "database".to_string(),
+        Some(GroupBy::Property(name)) => format!("property:{}", name),
+    }
+}
+
+fn parse_group_by_value(value: &str) -> Option<GroupBy> {
+    match value {
+        "" => None,
+        "database" => Some(GroupBy::Database),
+        _ => value
+            .strip_prefix("property:")
+            .map(|name| GroupBy::Property(name.to_string())),
+    }
+}
+
+/// 选择分列方式的下拉框
+fn resource_select(
+    current: &Option<GroupBy>,
+    events: &[CalendarEvent],
+    on_change: &Callback<Option<GroupBy>>,
+) -> Html {
+    let mut properties = groupable_properties(events);
+    // 当前按的属性暂时没有出现在事件中时仍然保留选项
+    if let Some(GroupBy::Property(name)) = current {
+        if !properties.contains(name) {
+            properties.push(name.clone());
+        }
+    }
+    let current_value = group_by_value(current);
+    let on_change = on_change.reform(|e: Event| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        parse_group_by_value(&input.value())
+    });
+
+    let option = |group_by: Option<GroupBy>, label: String| {
+        let value = group_by_value(&group_by);
+        let selected = value == current_value;
+        html! {
+            <option {selected} {value}>{label}</option>
+        }
+    };
+
+    html! {
+        <select class="resource-select" onchange={on_change}>
+            {option(None, "不分列".to_string())}
+            {option(Some(GroupBy::Database), "按数据库分列".to_string())}
+            {properties.into_iter().map(|name| {
+                option(Some(GroupBy::Property(name.clone())), format!("按「{}」分列", name))
+            }).collect::<Html>()}
+        </select>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct ResourceGridProps {
+    days: Vec<NaiveDate>,
+    groups: Vec<EventGroup>,
+    on_date_click: Callback<NaiveDate>,
+    on_event_click: Callback<CalendarEvent>,
+    on_event_change: Callback<CalendarEvent>,
+}
+
+/// 每个分组一列、每天一行的网格；拖拽只改变日期，不改变所在的分组
+#[function_component(ResourceGrid)]
+fn resource_grid(props: &ResourceGridProps) -> Html {
+    let drag = use_state(|| None::<DragState>);
+    let drop_target = use_state(|| None::<NaiveDate>);
+    let today = chrono::Local::now().naive_local().date();
+
+    if props.groups.is_empty() {
+        return html! {
+            <div class="no-events">
+                <span class="no-events-icon">{"☕️"}</span>
+                <p>{"这段时间没有安排"}</p>
+            </div>
+        };
+    }
+
+    let columns = format!(
+        "grid-template-columns: 5rem repeat({}, minmax(10rem, 1fr))",
+        props.groups.len()
+    );
+
+    let rows = props
+        .days
+        .iter()
+        .map(|&date| {
+            let cells = props
+                .groups
+                .iter()
+                .enumerate()
+                .map(|(column, group)| {
+                    let on_click = {
+                        let on_date_click = props.on_date_click.clone();
+                        Callback::from(move |_| on_date_click.emit(date))
+                    };
+                    html! {
+                        <div
+                            class={classes!(
+                                "resource-cell",
+                                (*drop_target == Some(date)).then_some("drop-target")
+                            )}
+                            tabindex="0"
+                            data-date={date.to_string()}
+                            data-column={column.to_string()}
+                            onclick={on_click}
+                            ondragover={on_drag_over(&drag, &drop_target, date)}
+                            ondrop={on_drop(&drag, &drop_target, date, &props.on_event_change)}
+                        >
+                            {group.events.iter().filter(|e| {
+                                e.start_date <= date && date <= e.end_date.unwrap_or(e.start_date)
+                            }).map(|event| {
+                                let on_event_click = {
+                                    let on_event_click = props.on_event_click.clone();
+                                    let event = event.clone();
+                                    Callback::from(move |e: MouseEvent| {
+                                        e.stop_propagation();
+                                        on_event_click.emit(event.clone())
+                                    })
+                                };
+                                let draggable = is_draggable(event);
+                                let is_last_day = date == event.end_date.unwrap_or(event.start_date);
+                                html! {
+                                    <div
+                                        class="week-event-card"
+                                        style={format!("border-left-color: {}", event.color)}
+                                        title={event.tooltip()}
+                                        tabindex="0"
+                                        data-event-id={event.id.clone()}
+                                        onclick={on_event_click}
+                                        draggable={draggable.to_string()}
+                                        ondragstart={on_drag_start(&drag, event, move |_| DragMode::Move { anchor: date })}
+                                        ondragend={on_drag_end(&drag, &drop_target)}
+                                    >
+                                        <div class="event-title">{&event.title}{event_badges(event)}</div>
+                                        {event_subtitle(event)}
+                                        {if draggable && is_last_day {
+                                            html! {
+                                                <span
+                                                    class="resize-handle"
+                                                    draggable="true"
+                                                    title="拖动调整结束日期"
+                                                    ondragstart={on_drag_start(&drag, event, |_| DragMode::Resize)}
+                                                    ondragend={on_drag_end(&drag, &drop_target)}
+                                                />
+                                            }
+                                        } else {
+                                            html! {}
+                                        }}
+                                    </div>
+                                }
+                            }).collect::<Html>()}
+                        </div>
+                    }
+                })
+                .collect::<Html>();
+
+            html! {
+                <>
+                    <div class={classes!("resource-date", (date == today).then_some("today"))}>
+                        <span class="week-day-name">{weekday_name(date.weekday())}</span>
+                        <span class="week-day-number">{date.day()}</span>
+                    </div>
+                    {cells}
+                </>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="resource-grid" style={columns}>
+            <div class="resource-corner" />
+            {props.groups.iter().map(|group| html! {
+                <div class="resource-header">
+                    {if let Some(color) = &group.color {
+                        html! { <span class="db-color-dot" style={format!("background-color: {}", color)} /> }
+                    } else {
+                        html! {}
+                    }}
+                    <span>{&group.name}</span>
+                    <span class="resource-count">{group.events.len()}</span>
+                </div>
+            }).collect::<Html>()}
+            {rows}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn event(id: &str, start: NaiveDate, end: Option<NaiveDate>) -> CalendarEvent {
+        CalendarEvent {
+            end_date: end,
+            ..CalendarEvent::fixture(id, start)
+        }
+    }
+
+    fn lanes(laid_out: &[LaidOutEvent]) -> Vec<(String, usize)> {
+        let mut lanes: Vec<(String, usize)> = laid_out
+            .iter()
+            .map(|item| (item.event.id.clone(), item.lane))
+            .collect();
+        lanes.sort();
+        lanes
+    }
+
+    #[test]
+    fn lanes_do_not_depend_on_input_order() {
+        let events = vec![
+            event("a", date(2026, 3, 2), Some(date(2026, 3, 4))),
+            event("b", date(2026, 3, 3), None),
+            event("c", date(2026, 3, 2), Some(date(2026, 3, 10))),
+            event("d", date(2026, 3, 5), Some(date(2026, 3, 6))),
+            event("e", date(2026, 3, 3), Some(date(2026, 3, 3))),
+        ];
+        let start = date(2026, 3, 1);
+        let end = date(2026, 3, 31);
+        let expected = lanes(&assign_lanes(&events, start, end));
+
+        let mut reversed = events.clone();
+        reversed.reverse();
+        assert_eq!(lanes(&assign_lanes(&reversed, start, end)), expected);
+
+        let mut rotated = events.clone();
+        rotated.rotate_left(2);
+        assert_eq!(lanes(&assign_lanes(&rotated, start, end)), expected);
+
+        // 较长的 c 在最上面，同一天开始的 a 在第二行
+        assert_eq!(expected[2], ("c".to_string(), 0));
+        assert_eq!(expected[0], ("a".to_string(), 1));
+    }
+
+    #[test]
+    fn event_keeps_its_lane_across_weeks() {
+        // 2026-03-05（周四）到 03-11（周三）跨过周一 03-09
+        let events = vec![
+            event("short", date(2026, 3, 5), None),
+            event("long", date(2026, 3, 5), Some(date(2026, 3, 11))),
+            event("next-week", date(2026, 3, 9), None),
+        ];
+        let laid_out = assign_lanes(&events, date(2026, 2, 23), date(2026, 4, 5));
+        let long = laid_out.iter().find(|e| e.event.id == "long").unwrap();
+        assert_eq!(long.lane, 0);
+        // 下一周中它仍然占着第 0 行，同一天的事件排在下面
+        let next_week = laid_out.iter().find(|e| e.event.id == "next-week").unwrap();
+        assert_eq!(next_week.lane, 1);
+        assert!(long.start <= date(2026, 3, 9) && date(2026, 3, 9) <= long.end);
+    }
+
+    #[test]
+    fn clips_events_to_the_range() {
+        let events = vec![
+            event("before", date(2026, 2, 1), Some(date(2026, 2, 10))),
+            event("overlap", date(2026, 2, 20), Some(date(2026, 3, 3))),
+            event("after", date(2026, 4, 10), None),
+            event("inside", date(2026, 3, 15), None),
+            // 结束早于开始时按单日处理
+            event("reversed", date(2026, 3, 20), Some(date(2026, 3, 18))),
+        ];
+        let laid_out = assign_lanes(&events, date(2026, 2, 23), date(2026, 4, 5));
+        let ids: Vec<&str> = laid_out.iter().map(|e| e.event.id.as_str()).collect();
+        assert_eq!(ids, vec!["overlap", "inside", "reversed"]);
+
+        let overlap = &laid_out[0];
+        assert_eq!(overlap.start, date(2026, 2, 23));
+        assert_eq!(overlap.end, date(2026, 3, 3));
+        assert_eq!(laid_out[2].start, laid_out[2].end);
+    }
+}
@@ -0,0 +1,279 @@
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::api::NotionClient;
+use crate::types::{blocks_to_description, BlockContent, NotionBlock};
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct EventBodyProps {
+    pub api_key: String,
+    pub page_id: String,
+    /// 正文加载或修改后的纯文本，用于缓存到事件的 description
+    pub on_loaded: Callback<String>,
+}
+
+/// 页面正文：预览常见块，可编辑段落、追加新段落
+#[function_component(EventBody)]
+pub fn event_body(props: &EventBodyProps) -> Html {
+    // None = 加载中
+    let blocks = use_state(|| None::<Vec<NotionBlock>>);
+    let error_message = use_state(|| None::<String>);
+    // (块 id, 编辑中的文字)
+    let editing = use_state(|| None::<(String, String)>);
+    let new_text = use_state(String::new);
+    let is_saving = use_state(|| false);
+
+    // 加载正文
+    {
+        let blocks = blocks.clone();
+        let error_message = error_message.clone();
+        let api_key = props.api_key.clone();
+        let on_loaded = props.on_loaded.clone();
+        use_effect_with(props.page_id.clone(), move |page_id: &String| {
+            let page_id = page_id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let client = NotionClient::new(api_key);
+                match client.get_block_children(&page_id).await {
+                    Ok(loaded) => {
+                        on_loaded.emit(blocks_to_description(&loaded));
+                        blocks.set(Some(loaded));
+                    }
+                    Err(e) => {
+                        error_message.set(Some(format!("加载内容失败: {}", e)));
+                        blocks.set(Some(Vec::new()));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_new_text_change = {
+        let new_text = new_text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            new_text.set(input.value());
+        })
+    };
+
+    // 空行分隔的文字各自成为一个段落
+    let on_append = {
+        let blocks = blocks.clone();
+        let error_message = error_message.clone();
+        let new_text = new_text.clone();
+        let is_saving = is_saving.clone();
+        let api_key = props.api_key.clone();
+        let page_id = props.page_id.clone();
+        let on_loaded = props.on_loaded.clone();
+        Callback::from(move |_: MouseEvent| {
+            let paragraphs: Vec<String> = new_text
+                .split("\n\n")
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+            if paragraphs.is_empty() {
+                return;
+            }
+
+            let blocks = blocks.clone();
+            let error_message = error_message.clone();
+            let new_text = new_text.clone();
+            let is_saving = is_saving.clone();
+            let api_key = api_key.clone();
+            let page_id = page_id.clone();
+            let on_loaded = on_loaded.clone();
+
+            is_saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let client = NotionClient::new(api_key);
+                match client.append_paragraphs(&page_id, &paragraphs).await {
+                    Ok(created) => {
+                        let mut updated = (*blocks).clone().unwrap_or_default();
+                        updated.extend(created);
+                        on_loaded.emit(blocks_to_description(&updated));
+                        blocks.set(Some(updated));
+                        new_text.set(String::new());
+                        error_message.set(None);
+                    }
+                    Err(e) => error_message.set(Some(format!("追加失败: {}", e))),
+                }
+                is_saving.set(false);
+            });
+        })
+    };
+
+    let on_edit_change = {
+        let editing = editing.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            if let Some((ref id, _)) = *editing {
+                editing.set(Some((id.clone(), input.value())));
+            }
+        })
+    };
+
+    let on_edit_cancel = {
+        let editing = editing.clone();
+        Callback::from(move |_: MouseEvent| editing.set(None))
+    };
+
+    let on_edit_save = {
+        let blocks = blocks.clone();
+        let error_message = error_message.clone();
+        let editing = editing.clone();
+        let is_saving = is_saving.clone();
+        let api_key = props.api_key.clone();
+        let on_loaded = props.on_loaded.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some((block_id, text)) = (*editing).clone() else {
+                return;
+            };
+
+            let blocks = blocks.clone();
+            let error_message = error_message.clone();
+            let editing = editing.clone();
+            let is_saving = is_saving.clone();
+            let api_key = api_key.clone();
+            let on_loaded = on_loaded.clone();
+
+            is_saving.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                let client = NotionClient::new(api_key);
+                match client.update_paragraph(&block_id, &text).await {
+                    Ok(saved) => {
+                        let mut updated = (*blocks).clone().unwrap_or_default();
+                        if let Some(block) = updated.iter_mut().find(|b| b.id == saved.id) {
+                            *block = saved;
+                        }
+                        on_loaded.emit(blocks_to_description(&updated));
+                        blocks.set(Some(updated));
+                        editing.set(None);
+                        error_message.set(None);
+                    }
+                    Err(e) => error_message.set(Some(format!("保存内容失败: {}", e))),
+                }
+                is_saving.set(false);
+            });
+        })
+    };
+
+    let render_block = |(block, number): (&NotionBlock, usize)| -> Html {
+        match &block.content {
+            BlockContent::Paragraph { text, plain } => {
+                if let Some((ref id, ref draft)) = *editing {
+                    if *id == block.id {
+                        return html! {
+                            <div class="body-editor">
+                                <textarea
+                                    rows="3"
+                                    value={draft.clone()}
+                                    oninput={on_edit_change.clone()}
+                                />
+                                <div class="body-editor-actions">
+                                    <button type="button" class="btn-secondary" onclick={on_edit_cancel.clone()}>
+                                        {"取消"}
+                                    </button>
+                                    <button
+                                        type="button"
+                                        class="btn-primary"
+                                        disabled={*is_saving}
+                                        onclick={on_edit_save.clone()}
+                                    >
+                                        {"保存"}
+                                    </button>
+                                </div>
+                            </div>
+                        };
+                    }
+                }
+
+                // 带格式、链接或提及的段落整段改写会变成纯文本，只读展示
+                if !plain {
+                    return html! {
+                        <p class="body-paragraph readonly" title="含格式的段落请在 Notion 中编辑">{text}</p>
+                    };
+                }
+
+                let on_start_edit = {
+                    let editing = editing.clone();
+                    let id = block.id.clone();
+                    let text = text.clone();
+                    Callback::from(move |_: MouseEvent| {
+                        editing.set(Some((id.clone(), text.clone())))
+                    })
+                };
+                html! {
+                    <p class="body-paragraph" title="点击编辑" onclick={on_start_edit}>
+                        {if text.is_empty() { "\u{00a0}" } else { text.as_str() }}
+                    </p>
+                }
+            }
+            BlockContent::Heading { level, text } => match level {
+                1 => html! { <h4 class="body-heading-1">{text}</h4> },
+                2 => html! { <h5 class="body-heading-2">{text}</h5> },
+                _ => html! { <h6 class="body-heading-3">{text}</h6> },
+            },
+            BlockContent::ToDo { text, checked } => html! {
+                <div class={classes!("body-todo", checked.then_some("checked"))}>
+                    {if *checked { "☑ " } else { "☐ " }}{text}
+                </div>
+            },
+            BlockContent::BulletedListItem(text) => html! {
+                <div class="body-list-item">{"• "}{text}</div>
+            },
+            BlockContent::NumberedListItem(text) => html! {
+                <div class="body-list-item">{format!("{}. ", number)}{text}</div>
+            },
+            BlockContent::Unsupported(kind) => html! {
+                <div class="body-unsupported">{format!("[{}]", kind)}</div>
+            },
+        }
+    };
+
+    html! {
+        <div class="form-group event-body">
+            <label>{"内容"}</label>
+            <div class="body-preview">
+                {match *blocks {
+                    None => html! { <div class="help-text">{"加载中..."}</div> },
+                    Some(ref blocks) if blocks.is_empty() => html! {
+                        <div class="help-text">{"页面还没有内容"}</div>
+                    },
+                    Some(ref blocks) => {
+                        // 连续的编号列表项从 1 开始计数
+                        let numbers = blocks.iter().scan(0, |count, block| {
+                            *count = match block.content {
+                                BlockContent::NumberedListItem(_) => *count + 1,
+                                _ => 0,
+                            };
+                            Some(*count)
+                        });
+                        blocks.iter().zip(numbers).map(render_block).collect::<Html>()
+                    }
+                }}
+            </div>
+            <textarea
+                class="body-new-text"
+                rows="2"
+                value={(*new_text).clone()}
+                oninput={on_new_text_change}
+                placeholder="追加段落（空行分隔多段）"
+            />
+            <div class="body-editor-actions">
+                <button
+                    type="button"
+                    class="btn-secondary"
+                    disabled={*is_saving || new_text.trim().is_empty()}
+                    onclick={on_append}
+                >
+                    {"追加"}
+                </button>
+            </div>
+            {if let Some(ref error) = *error_message {
+                html! { <div class="alert alert-error">{error}</div> }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
@@ -1,17 +1,42 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveTime};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::types::{CalendarEvent, DatabaseConfig};
+use crate::api;
+use crate::components::event_body::EventBody;
+use crate::components::property_input::PropertyInput;
+use crate::types::{
+    CalendarEvent, DatabaseConfig, EventDraft, EventProperty, NotionUser, PropertyType,
+    PropertyValue,
+};
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct EventModalProps {
     pub event: Option<CalendarEvent>, // None = 新建事件
     pub databases: Vec<DatabaseConfig>,
     pub selected_date: Option<NaiveDate>,
-    pub on_save: Callback<(String, String, String, Option<String>)>, // (title, date, db_id, page_id)
+    pub on_save: Callback<EventDraft>,
     pub on_delete: Callback<String>, // page_id
     pub on_close: Callback<()>,
+    /// 有 API Key 时才加载页面正文
+    pub api_key: Option<String>,
+    pub on_description_change: Callback<(String, String)>, // (page_id, 正文纯文本)
+}
+
+/// 新建非全天事件时的默认开始时间
+const DEFAULT_START_TIME: &str = "09:00";
+/// 新建非全天事件时的默认结束时间
+const DEFAULT_END_TIME: &str = "10:00";
+
+fn format_time(time: Option<NaiveTime>, default: &str) -> String {
+    time.map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_else(|| default.to_string())
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .ok()
 }
 
 #[function_component(EventModal)]
@@ -28,7 +53,7 @@ pub fn event_modal(props: &EventModalProps) -> Html {
             .unwrap_or_default()
     });
 
-    let date = use_state(|| {
+    let start_date = use_state(|| {
         props
             .event
             .as_ref()
@@ -41,6 +66,32 @@ pub fn event_modal(props: &EventModalProps) -> Html {
             })
     });
 
+    // 空字符串表示没有结束日期
+    let end_date = use_state(|| {
+        props
+            .event
+            .as_ref()
+            .and_then(|e| e.end_date)
+            .map(|d| d.to_string())
+            .unwrap_or_default()
+    });
+
+    let all_day = use_state(|| props.event.as_ref().map(|e| e.all_day).unwrap_or(true));
+
+    let start_time = use_state(|| {
+        format_time(
+            props.event.as_ref().and_then(|e| e.start_time),
+            DEFAULT_START_TIME,
+        )
+    });
+
+    let end_time = use_state(|| {
+        format_time(
+            props.event.as_ref().and_then(|e| e.end_time),
+            DEFAULT_END_TIME,
+        )
+    });
+
     let selected_db = use_state(|| {
         props
             .event
@@ -55,8 +106,77 @@ pub fn event_modal(props: &EventModalProps) -> Html {
             })
     });
 
+    let properties = use_state(|| {
+        props
+            .event
+            .as_ref()
+            .map(|e| e.properties.clone())
+            .unwrap_or_default()
+    });
+
+    let users = use_state(Vec::<NotionUser>::new);
+
     let error_message = use_state(|| None::<String>);
 
+    // 新建时按数据库结构给映射的属性准备空值，换数据库时重新获取
+    {
+        let properties = properties.clone();
+        let api_key = props.api_key.clone();
+        let config = props
+            .databases
+            .iter()
+            .find(|db| db.id == *selected_db)
+            .cloned();
+        use_effect_with((*selected_db).clone(), move |_| {
+            if is_new {
+                properties.set(Vec::new());
+                if let (Some(api_key), Some(config)) = (api_key, config) {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let client = api::NotionClient::new(api_key);
+                        let Ok(database) = client.get_database(&config.notion_database_id).await
+                        else {
+                            return;
+                        };
+                        properties.set(
+                            config
+                                .extra_properties
+                                .iter()
+                                .filter_map(|mapping| {
+                                    let schema = database.properties.get(&mapping.property)?;
+                                    Some(EventProperty {
+                                        name: mapping.property.clone(),
+                                        slot: mapping.slot,
+                                        value: PropertyValue::empty(schema.kind)?,
+                                    })
+                                })
+                                .collect(),
+                        );
+                    });
+                }
+            }
+            || ()
+        });
+    }
+
+    // 有人员属性时加载工作区成员供选择
+    {
+        let users = users.clone();
+        let api_key = props.api_key.clone();
+        let has_people = properties
+            .iter()
+            .any(|p| p.value.property_type() == PropertyType::People);
+        use_effect_with(has_people, move |has_people| {
+            if let (true, Some(api_key)) = (*has_people, api_key) {
+                wasm_bindgen_futures::spawn_local(async move {
+                    if let Ok(list) = api::NotionClient::new(api_key).list_users().await {
+                        users.set(list);
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
     let on_title_change = {
         let title = title.clone();
         Callback::from(move |e: Event| {
@@ -65,11 +185,19 @@ pub fn event_modal(props: &EventModalProps) -> Html {
         })
     };
 
-    let on_date_change = {
-        let date = date.clone();
+    let on_input_change = |handle: &UseStateHandle<String>| {
+        let handle = handle.clone();
         Callback::from(move |e: Event| {
             let input: HtmlInputElement = e.target_unchecked_into();
-            date.set(input.value());
+            handle.set(input.value());
+        })
+    };
+
+    let on_all_day_change = {
+        let all_day = all_day.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            all_day.set(input.checked());
         })
     };
 
@@ -83,8 +211,13 @@ pub fn event_modal(props: &EventModalProps) -> Html {
 
     let on_submit = {
         let title = title.clone();
-        let date = date.clone();
+        let start_date = start_date.clone();
+        let end_date = end_date.clone();
+        let all_day = all_day.clone();
+        let start_time = start_time.clone();
+        let end_time = end_time.clone();
         let selected_db = selected_db.clone();
+        let properties = properties.clone();
         let page_id = page_id.clone();
         let on_save = props.on_save.clone();
         let on_close = props.on_close.clone();
@@ -98,12 +231,52 @@ pub fn event_modal(props: &EventModalProps) -> Html {
                 return;
             }
 
-            on_save.emit((
-                (*title).clone(),
-                (*date).clone(),
-                (*selected_db).clone(),
-                page_id.clone(),
-            ));
+            let Ok(start) = NaiveDate::parse_from_str(&start_date, "%Y-%m-%d") else {
+                error_message.set(Some("开始日期格式不正确".to_string()));
+                return;
+            };
+
+            let end = if end_date.is_empty() {
+                None
+            } else {
+                match NaiveDate::parse_from_str(&end_date, "%Y-%m-%d") {
+                    Ok(date) => Some(date),
+                    Err(_) => {
+                        error_message.set(Some("结束日期格式不正确".to_string()));
+                        return;
+                    }
+                }
+            };
+
+            let (start_at, end_at) = if *all_day {
+                (None, None)
+            } else {
+                let Some(start_at) = parse_time(&start_time) else {
+                    error_message.set(Some("开始时间格式不正确".to_string()));
+                    return;
+                };
+                (Some(start_at), end.and_then(|_| parse_time(&end_time)))
+            };
+
+            if let Some(end) = end {
+                let starts = (start, start_at.unwrap_or(NaiveTime::MIN));
+                let ends = (end, end_at.unwrap_or(NaiveTime::MIN));
+                if ends < starts {
+                    error_message.set(Some("结束时间不能早于开始时间".to_string()));
+                    return;
+                }
+            }
+
+            on_save.emit(EventDraft {
+                title: (*title).clone(),
+                database_id: (*selected_db).clone(),
+                page_id: page_id.clone(),
+                start_date: start,
+                end_date: end,
+                start_time: start_at,
+                end_time: end_at,
+                properties: (*properties).clone(),
+            });
             on_close.emit(());
         })
     };
@@ -121,6 +294,9 @@ pub fn event_modal(props: &EventModalProps) -> Html {
         })
     };
 
+    // Notion 页面不能在数据库之间移动，已有事件只能在原数据库中编辑
+    let db_locked = page_id.is_some();
+
     html! {
         <div class="modal-overlay" onclick={props.on_close.reform(|_| ())}>
             <div class="modal" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
@@ -145,18 +321,115 @@ pub fn event_modal(props: &EventModalProps) -> Html {
                         </div>
 
                         <div class="form-group">
-                            <label>{"日期"}</label>
-                            <input
-                                type="date"
-                                value={(*date).clone()}
-                                onchange={on_date_change}
-                                required={true}
-                            />
+                            <label class="checkbox-label">
+                                <input
+                                    type="checkbox"
+                                    checked={*all_day}
+                                    onchange={on_all_day_change}
+                                />
+                                {"全天"}
+                            </label>
+                        </div>
+
+                        <div class="form-row">
+                            <div class="form-group">
+                                <label>{"开始"}</label>
+                                <input
+                                    type="date"
+                                    value={(*start_date).clone()}
+                                    onchange={on_input_change(&start_date)}
+                                    required={true}
+                                />
+                                {if *all_day {
+                                    html! {}
+                                } else {
+                                    html! {
+                                        <input
+                                            type="time"
+                                            class="time-input"
+                                            value={(*start_time).clone()}
+                                            onchange={on_input_change(&start_time)}
+                                            required={true}
+                                        />
+                                    }
+                                }}
+                            </div>
+                            <div class="form-group">
+                                <label>{"结束"}</label>
+                                <input
+                                    type="date"
+                                    value={(*end_date).clone()}
+                                    min={(*start_date).clone()}
+                                    onchange={on_input_change(&end_date)}
+                                />
+                                {if *all_day || end_date.is_empty() {
+                                    html! {}
+                                } else {
+                                    html! {
+                                        <input
+                                            type="time"
+                                            class="time-input"
+                                            value={(*end_time).clone()}
+                                            onchange={on_input_change(&end_time)}
+                                        />
+                                    }
+                                }}
+                                <span class="help-text">{"留空表示没有结束日期"}</span>
+                            </div>
                         </div>
 
+                        {properties.iter().enumerate().map(|(index, property)| {
+                            let on_change = {
+                                let properties = properties.clone();
+                                Callback::from(move |value: PropertyValue| {
+                                    let mut updated = (*properties).clone();
+                                    if let Some(property) = updated.get_mut(index) {
+                                        property.value = value;
+                                    }
+                                    properties.set(updated);
+                                })
+                            };
+                            let EventProperty { name, value, .. } = property;
+                            html! {
+                                <div class="form-group">
+                                    <label>{name}</label>
+                                    <PropertyInput
+                                        value={value.clone()}
+                                        on_change={on_change}
+                                        users={(*users).clone()}
+                                    />
+                                </div>
+                            }
+                        }).collect::<Html>()}
+
+                        {match (&props.api_key, &page_id) {
+                            (Some(api_key), Some(page_id)) => {
+                                let on_loaded = {
+                                    let on_description_change = props.on_description_change.clone();
+                                    let page_id = page_id.clone();
+                                    Callback::from(move |text: String| {
+                                        on_description_change.emit((page_id.clone(), text))
+                                    })
+                                };
+                                html! {
+                                    <EventBody
+                                        api_key={api_key.clone()}
+                                        page_id={page_id.clone()}
+                                        on_loaded={on_loaded}
+                                    />
+                                }
+                            }
+                            _ => html! {},
+                        }}
+
                         <div class="form-group">
                             <label>{"所属数据库"}</label>
-                            <select value={(*selected_db).clone()} onchange={on_db_change}>
+                            <select
+                                value={(*selected_db).clone()}
+                                onchange={on_db_change}
+                                disabled={db_locked}
+                                title={db_locked.then_some("已有事件不能更换数据库")}
+                            >
                                 {props.databases.iter().map(|db| {
                                     html! {
                                         <option value={db.id.clone()}>
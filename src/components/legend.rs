@@ -0,0 +1,35 @@
+use yew::prelude::*;
+
+use crate::types::CalendarEvent;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ColorLegendProps {
+    pub events: Vec<CalendarEvent>,
+}
+
+/// 颜色图例：列出当前事件中按属性取色的选项
+#[function_component(ColorLegend)]
+pub fn color_legend(props: &ColorLegendProps) -> Html {
+    let mut entries: Vec<(&str, &str)> = props
+        .events
+        .iter()
+        .filter_map(|e| Some((e.color_label.as_deref()?, e.color.as_str())))
+        .collect();
+    entries.sort();
+    entries.dedup();
+
+    if entries.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="color-legend">
+            {entries.into_iter().map(|(label, color)| html! {
+                <span class="legend-item">
+                    <span class="legend-dot" style={format!("background-color: {}", color)} />
+                    {label}
+                </span>
+            }).collect::<Html>()}
+        </div>
+    }
+}
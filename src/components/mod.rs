@@ -1,7 +1,26 @@
+pub mod agenda;
 pub mod calendar;
+pub mod event_body;
 pub mod event_modal;
+pub mod legend;
+pub mod property_input;
+pub mod quick_add;
+pub mod search;
 pub mod settings;
+pub mod shortcuts;
+pub mod sidebar;
+pub mod timeline;
+pub mod toast;
+pub mod trash;
+pub mod year;
 
 pub use calendar::Calendar;
 pub use event_modal::EventModal;
+pub use legend::ColorLegend;
+pub use quick_add::QuickAddBar;
+pub use search::SearchPalette;
 pub use settings::SettingsPanel;
+pub use shortcuts::{CommandPalette, ShortcutHelp};
+pub use sidebar::DatabaseSidebar;
+pub use toast::UndoToast;
+pub use trash::TrashPanel;
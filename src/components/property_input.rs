@@ -0,0 +1,321 @@
+use chrono::{NaiveDate, NaiveTime};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::types::{
+    format_notion_date, parse_notion_date, DateValue, NotionUser, PropertyValue, RichText,
+    SelectOption,
+};
+
+/// 页面对象里的人员属性最多返回的人数，达到时可能还有更多，写回会丢掉其余的
+const PAGE_PEOPLE_LIMIT: usize = 25;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct PropertyInputProps {
+    pub value: PropertyValue,
+    pub on_change: Callback<PropertyValue>,
+    /// 人员属性可选的成员
+    pub users: Vec<NotionUser>,
+}
+
+/// 按属性类型选择输入框；关联、公式等只读类型只显示文本
+#[function_component(PropertyInput)]
+pub fn property_input(props: &PropertyInputProps) -> Html {
+    let value = props.value.clone();
+    let on_change = props.on_change.clone();
+    // 把输入框的值转成新的属性值
+    let emit_with = move |convert: fn(&PropertyValue, &HtmlInputElement) -> PropertyValue| {
+        let value = value.clone();
+        let on_change = on_change.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            on_change.emit(convert(&value, &input));
+        })
+    };
+
+    let text = props.value.plain_text();
+
+    match &props.value {
+        PropertyValue::Title { .. } | PropertyValue::RichText { .. } => html! {
+            <input
+                type="text"
+                value={text}
+                onchange={emit_with(|value, input| {
+                    let rich_text = vec![RichText {
+                        plain_text: input.value(),
+                        href: None,
+                    }];
+                    match value {
+                        PropertyValue::Title { .. } => PropertyValue::Title { title: rich_text },
+                        _ => PropertyValue::RichText { rich_text },
+                    }
+                })}
+            />
+        },
+        PropertyValue::Number { .. } => html! {
+            <input
+                type="number"
+                step="any"
+                value={text}
+                onchange={emit_with(|_, input| PropertyValue::Number {
+                    number: input.value().trim().parse().ok(),
+                })}
+            />
+        },
+        PropertyValue::Select { .. } | PropertyValue::Status { .. } => html! {
+            <input
+                type="text"
+                value={text}
+                placeholder="选项名称"
+                onchange={emit_with(|value, input| {
+                    let name = input.value().trim().to_string();
+                    let option = match value {
+                        PropertyValue::Select { select: option }
+                        | PropertyValue::Status { status: option } => option.clone(),
+                        _ => None,
+                    };
+                    // 名称没变时保留原选项（含颜色）
+                    let option = match option {
+                        Some(option) if option.name == name => Some(option),
+                        _ if name.is_empty() => None,
+                        _ => Some(SelectOption {
+                            id: None,
+                            name,
+                            color: None,
+                        }),
+                    };
+                    match value {
+                        PropertyValue::Status { .. } => PropertyValue::Status { status: option },
+                        _ => PropertyValue::Select { select: option },
+                    }
+                })}
+            />
+        },
+        PropertyValue::MultiSelect { .. } => html! {
+            <input
+                type="text"
+                value={text}
+                placeholder="多个选项用逗号分隔"
+                onchange={emit_with(|value, input| {
+                    let existing = match value {
+                        PropertyValue::MultiSelect { multi_select } => multi_select.clone(),
+                        _ => Vec::new(),
+                    };
+                    let multi_select = input
+                        .value()
+                        .split([',', '，'])
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty())
+                        .map(|name| {
+                            existing
+                                .iter()
+                                .find(|o| o.name == name)
+                                .cloned()
+                                .unwrap_or_else(|| SelectOption {
+                                    id: None,
+                                    name: name.to_string(),
+                                    color: None,
+                                })
+                        })
+                        .collect();
+                    PropertyValue::MultiSelect { multi_select }
+                })}
+            />
+        },
+        PropertyValue::Date { date } => html! {
+            <DateInput value={date.clone()} on_change={props.on_change.clone()} />
+        },
+        PropertyValue::People { people } if people.len() < PAGE_PEOPLE_LIMIT => html! {
+            <PeopleInput
+                people={people.clone()}
+                users={props.users.clone()}
+                on_change={props.on_change.clone()}
+            />
+        },
+        PropertyValue::Checkbox { checkbox } => html! {
+            <input
+                type="checkbox"
+                checked={*checkbox}
+                onchange={emit_with(|_, input| PropertyValue::Checkbox {
+                    checkbox: input.checked(),
+                })}
+            />
+        },
+        PropertyValue::Url { .. }
+        | PropertyValue::Email { .. }
+        | PropertyValue::PhoneNumber { .. } => {
+            let input_type = match props.value {
+                PropertyValue::Url { .. } => "url",
+                PropertyValue::Email { .. } => "email",
+                _ => "tel",
+            };
+            html! {
+                <input
+                    type={input_type}
+                    value={text}
+                    onchange={emit_with(|value, input| {
+                        let text = Some(input.value().trim().to_string()).filter(|t| !t.is_empty());
+                        match value {
+                            PropertyValue::Url { .. } => PropertyValue::Url { url: text },
+                            PropertyValue::Email { .. } => PropertyValue::Email { email: text },
+                            _ => PropertyValue::PhoneNumber { phone_number: text },
+                        }
+                    })}
+                />
+            }
+        }
+        _ => html! {
+            <span class="property-readonly">
+                {if text.is_empty() { "—".to_string() } else { text }}
+            </span>
+        },
+    }
+}
+
+fn input_value(node: &NodeRef) -> String {
+    node.cast::<HtmlInputElement>()
+        .map(|input| input.value())
+        .unwrap_or_default()
+}
+
+/// 日期和可选的时间，日期为空时为 None
+fn read_date_time(date: &NodeRef, time: &NodeRef) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let date = NaiveDate::parse_from_str(&input_value(date), "%Y-%m-%d").ok()?;
+    Some((
+        date,
+        NaiveTime::parse_from_str(&input_value(time), "%H:%M").ok(),
+    ))
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct DateInputProps {
+    value: Option<DateValue>,
+    on_change: Callback<PropertyValue>,
+}
+
+/// 日期属性：开始和结束各有日期和可选的时间，时间留空表示全天
+#[function_component(DateInput)]
+fn date_input(props: &DateInputProps) -> Html {
+    let start_date = use_node_ref();
+    let start_time = use_node_ref();
+    let end_date = use_node_ref();
+    let end_time = use_node_ref();
+
+    let parsed = |value: Option<&String>| value.and_then(|v| parse_notion_date(v));
+    let start = parsed(props.value.as_ref().map(|d| &d.start));
+    let end = parsed(props.value.as_ref().and_then(|d| d.end.as_ref()));
+    let date_text = |value: Option<(NaiveDate, Option<NaiveTime>)>| {
+        value.map(|(date, _)| date.to_string()).unwrap_or_default()
+    };
+    let time_text = |value: Option<(NaiveDate, Option<NaiveTime>)>| {
+        value
+            .and_then(|(_, time)| time)
+            .map(|time| time.format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    let on_input_change = {
+        let refs = [
+            start_date.clone(),
+            start_time.clone(),
+            end_date.clone(),
+            end_time.clone(),
+        ];
+        let on_change = props.on_change.clone();
+        Callback::from(move |_: Event| {
+            let [start_date, start_time, end_date, end_time] = &refs;
+            let Some((date, time)) = read_date_time(start_date, start_time) else {
+                on_change.emit(PropertyValue::Date { date: None });
+                return;
+            };
+            // Notion 要求开始和结束同为全天或同带时间
+            let end = read_date_time(end_date, end_time)
+                .map(|(end, end_time)| format_notion_date(end, time.and(end_time.or(time))));
+            on_change.emit(PropertyValue::date(format_notion_date(date, time), end));
+        })
+    };
+
+    html! {
+        <div class="property-date">
+            <input
+                ref={start_date}
+                type="date"
+                value={date_text(start)}
+                onchange={on_input_change.clone()}
+            />
+            <input
+                ref={start_time}
+                type="time"
+                value={time_text(start)}
+                onchange={on_input_change.clone()}
+            />
+            <input
+                ref={end_date}
+                type="date"
+                value={date_text(end)}
+                onchange={on_input_change.clone()}
+            />
+            <input
+                ref={end_time}
+                type="time"
+                value={time_text(end)}
+                onchange={on_input_change}
+            />
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+struct PeopleInputProps {
+    people: Vec<NotionUser>,
+    users: Vec<NotionUser>,
+    on_change: Callback<PropertyValue>,
+}
+
+/// 人员属性：勾选工作区成员，已选中但不在成员列表中的人（如访客）也列出
+#[function_component(PeopleInput)]
+fn people_input(props: &PeopleInputProps) -> Html {
+    let choices: Vec<NotionUser> = props
+        .people
+        .iter()
+        .chain(
+            props
+                .users
+                .iter()
+                .filter(|user| !props.people.iter().any(|p| p.id == user.id)),
+        )
+        .cloned()
+        .collect();
+
+    if choices.is_empty() {
+        return html! { <span class="property-readonly">{"没有可选的成员"}</span> };
+    }
+
+    html! {
+        <div class="property-people">
+            {choices.into_iter().map(|user| {
+                let checked = props.people.iter().any(|p| p.id == user.id);
+                let on_toggle = {
+                    let people = props.people.clone();
+                    let on_change = props.on_change.clone();
+                    let user = user.clone();
+                    Callback::from(move |e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        let mut people: Vec<NotionUser> =
+                            people.iter().filter(|p| p.id != user.id).cloned().collect();
+                        if input.checked() {
+                            people.push(user.clone());
+                        }
+                        on_change.emit(PropertyValue::People { people });
+                    })
+                };
+                html! {
+                    <label class="checkbox-label">
+                        <input type="checkbox" checked={checked} onchange={on_toggle} />
+                        {user.name.clone().unwrap_or(user.id)}
+                    </label>
+                }
+            }).collect::<Html>()}
+        </div>
+    }
+}
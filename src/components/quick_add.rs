@@ -0,0 +1,197 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::types::{DatabaseConfig, EventDraft};
+use crate::utils::format_date;
+use crate::utils::quick_add::{parse_quick_add, QuickAdd};
+
+/// 重复事件一次创建的次数
+const RECURRENCE_COUNT: usize = 8;
+
+/// 预览中的日期和时间
+fn when_text(parsed: &QuickAdd) -> String {
+    let mut text = format_date(&parsed.start_date);
+    if let Some(end) = parsed.end_date.filter(|end| *end != parsed.start_date) {
+        text = format!("{} - {}", text, format_date(&end));
+    }
+    match (parsed.start_time, parsed.end_time) {
+        (Some(start), Some(end)) => {
+            format!("{} {}-{}", text, start.format("%H:%M"), end.format("%H:%M"))
+        }
+        (Some(start), None) => format!("{} {}", text, start.format("%H:%M")),
+        _ => format!("{} 全天", text),
+    }
+}
+
+/// 要创建的页面；重复事件的每一次都是单独的页面
+fn drafts(parsed: &QuickAdd, database_id: &str) -> Vec<EventDraft> {
+    let dates = match parsed.recurrence {
+        Some(recurrence) => recurrence.occurrences(parsed.start_date, RECURRENCE_COUNT),
+        None => vec![parsed.start_date],
+    };
+    dates
+        .into_iter()
+        .map(|date| EventDraft {
+            title: parsed.title.clone(),
+            database_id: database_id.to_string(),
+            page_id: None,
+            start_date: date,
+            // 当天的时间段也要带上结束日期，否则写入 Notion 时会丢掉结束时间
+            end_date: parsed.end_date.or(parsed.end_time.map(|_| date)),
+            start_time: parsed.start_time,
+            end_time: parsed.end_time,
+            properties: Vec::new(),
+        })
+        .collect()
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct QuickAddBarProps {
+    pub databases: Vec<DatabaseConfig>,
+    pub on_save: Callback<EventDraft>,
+}
+
+/// 快速添加：输入一句话，解析出日期和时间后在默认数据库中创建页面
+#[function_component(QuickAddBar)]
+pub fn quick_add_bar(props: &QuickAddBarProps) -> Html {
+    let text = use_state(String::new);
+
+    // 默认数据库：第一个已连接 Notion 的数据库
+    let Some(database) = props
+        .databases
+        .iter()
+        .find(|db| !db.notion_database_id.is_empty())
+    else {
+        return html! {};
+    };
+
+    let today = chrono::Local::now().naive_local().date();
+    let parsed = parse_quick_add(&text, today);
+
+    let on_input = {
+        let text = text.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            text.set(input.value());
+        })
+    };
+
+    let on_submit = {
+        let text = text.clone();
+        let parsed = parsed.clone();
+        let database_id = database.id.clone();
+        let on_save = props.on_save.clone();
+        Callback::from(move |()| {
+            let Some(parsed) = parsed.as_ref() else {
+                return;
+            };
+            for draft in drafts(parsed, &database_id) {
+                on_save.emit(draft);
+            }
+            text.set(String::new());
+        })
+    };
+
+    let on_keydown = {
+        let text = text.clone();
+        let on_submit = on_submit.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => {
+                e.prevent_default();
+                on_submit.emit(());
+            }
+            "Escape" => text.set(String::new()),
+            _ => {}
+        })
+    };
+
+    let preview = match &parsed {
+        Some(parsed) => html! {
+            <div class="quick-add-preview">
+                <span class="quick-add-title">{&parsed.title}</span>
+                <span>{when_text(parsed)}</span>
+                {match parsed.recurrence {
+                    Some(recurrence) => html! {
+                        <span class="quick-add-recurrence">
+                            {format!("🔁 {}（创建接下来的 {} 次）", recurrence.describe(), RECURRENCE_COUNT)}
+                        </span>
+                    },
+                    None => html! {},
+                }}
+                <span class="quick-add-db">
+                    <span class="event-color-dot" style={format!("background-color: {}", database.color)} />
+                    {&database.name}
+                </span>
+            </div>
+        },
+        None if !text.trim().is_empty() => html! {
+            <div class="quick-add-preview quick-add-empty">{"还需要一个标题"}</div>
+        },
+        None => html! {},
+    };
+
+    html! {
+        <div class="quick-add">
+            <div class="quick-add-row">
+                <input
+                    class="quick-add-input"
+                    type="text"
+                    placeholder="快速添加，例如：明天下午3点 周会、下周五 交报告、every Monday standup"
+                    value={(*text).clone()}
+                    oninput={on_input}
+                    onkeydown={on_keydown}
+                />
+                <button
+                    class="btn-primary"
+                    disabled={parsed.is_none()}
+                    onclick={on_submit.reform(|_| ())}
+                >
+                    {"添加"}
+                </button>
+            </div>
+            {preview}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{format_notion_date, CalendarEvent};
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn same_day_range_keeps_end_for_each_occurrence() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let parsed = parse_quick_add("每天下午3点到5点 培训", today).unwrap();
+
+        let drafts = drafts(&parsed, "db");
+        assert_eq!(drafts.len(), RECURRENCE_COUNT);
+        for draft in drafts {
+            assert_eq!(draft.end_date, Some(draft.start_date));
+
+            let event = CalendarEvent {
+                end_date: draft.end_date,
+                start_time: draft.start_time,
+                end_time: draft.end_time,
+                all_day: draft.all_day(),
+                ..CalendarEvent::fixture("培训", draft.start_date)
+            };
+            let expected = format_notion_date(
+                draft.start_date,
+                Some(NaiveTime::from_hms_opt(17, 0, 0).unwrap()),
+            );
+            assert_eq!(event.notion_end(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn all_day_event_has_no_end() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 11).unwrap();
+        let parsed = parse_quick_add("明天 交报告", today).unwrap();
+
+        let drafts = drafts(&parsed, "db");
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].end_date, None);
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use gloo::timers::callback::Timeout;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::api;
+use crate::types::{CalendarEvent, DatabaseConfig};
+use crate::utils::format_date;
+
+/// 每组最多显示的结果数
+const MAX_RESULTS: usize = 20;
+/// 停止输入多久后再去 Notion 搜索
+const REMOTE_DEBOUNCE_MS: u32 = 300;
+/// 至少输入几个字才去 Notion 搜索
+const REMOTE_MIN_CHARS: usize = 2;
+/// 正文摘要的长度
+const SNIPPET_CHARS: usize = 40;
+
+/// 事件与搜索词的匹配得分，有搜索词完全没有匹配时为 None
+///
+/// 每个词分别计分后相加：标题完全相同最高，其次是标题开头、标题中间、
+/// 额外属性、正文。
+fn match_score(event: &CalendarEvent, terms: &[String]) -> Option<u32> {
+    let title = event.title.to_lowercase();
+    let properties: Vec<String> = event
+        .properties
+        .iter()
+        .map(|p| p.value.plain_text().to_lowercase())
+        .collect();
+    let description = event.description.as_deref().unwrap_or("").to_lowercase();
+
+    terms.iter().try_fold(0, |score, term| {
+        let term_score = if title == *term {
+            100
+        } else if title.starts_with(term.as_str()) {
+            60
+        } else if title.contains(term.as_str()) {
+            40
+        } else if properties.iter().any(|p| p.contains(term.as_str())) {
+            20
+        } else if description.contains(term.as_str()) {
+            10
+        } else {
+            return None;
+        };
+        Some(score + term_score)
+    })
+}
+
+/// 按得分从高到低排列匹配的事件，得分相同时离今天近的在前
+fn rank_events<'a>(
+    events: &'a [CalendarEvent],
+    query: &str,
+    today: NaiveDate,
+) -> Vec<&'a CalendarEvent> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(u32, i64, &CalendarEvent)> = events
+        .iter()
+        .filter_map(|event| {
+            let score = match_score(event, &terms)?;
+            let distance = (event.start_date - today).num_days().abs();
+            Some((score, distance, event))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, _, event)| event)
+        .collect()
+}
+
+/// Notion 的结果中去掉已经加载过的事件，它们已经在本地结果里参与排序
+///
+/// 每次从页面提取事件都会生成新的本地 id，所以按 Notion 页面 id 比较。
+fn not_loaded(loaded: &[CalendarEvent], remote: &[CalendarEvent]) -> Vec<CalendarEvent> {
+    let loaded_pages: HashSet<&str> = loaded
+        .iter()
+        .filter_map(|e| e.notion_page_id.as_deref())
+        .collect();
+    remote
+        .iter()
+        .filter(|e| {
+            e.notion_page_id
+                .as_deref()
+                .is_none_or(|id| !loaded_pages.contains(id))
+        })
+        .cloned()
+        .collect()
+}
+
+/// 标题没有匹配时，说明是在哪里匹配到的
+fn match_hint(event: &CalendarEvent, query: &str) -> Option<String> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let title = event.title.to_lowercase();
+    let term = terms.iter().find(|t| !title.contains(t.as_str()))?;
+
+    if let Some(property) = event
+        .properties
+        .iter()
+        .find(|p| p.value.plain_text().to_lowercase().contains(term.as_str()))
+    {
+        return Some(format!(
+            "{}：{}",
+            property.name,
+            property.value.plain_text()
+        ));
+    }
+
+    let description = event.description.as_deref()?;
+    let lower = description.to_lowercase();
+    let index = lower.find(term.as_str())?;
+    // 从匹配处往前留几个字作为上下文
+    let before = lower[..index].chars().count().saturating_sub(10);
+    let snippet: String = description
+        .chars()
+        .skip(before)
+        .take(SNIPPET_CHARS)
+        .collect();
+    Some(format!("…{}…", snippet.trim()))
+}
+
+fn event_date_text(event: &CalendarEvent) -> String {
+    let start = match event.start_time {
+        Some(time) => format!(
+            "{} {}",
+            format_date(&event.start_date),
+            time.format("%H:%M")
+        ),
+        None => format_date(&event.start_date),
+    };
+    match event.end_date {
+        Some(end) if end != event.start_date => format!("{} - {}", start, format_date(&end)),
+        _ => start,
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct SearchPaletteProps {
+    /// 已加载的所有事件
+    pub events: Vec<CalendarEvent>,
+    pub databases: Vec<DatabaseConfig>,
+    pub api_key: Option<String>,
+    pub on_select: Callback<CalendarEvent>,
+    pub on_close: Callback<()>,
+}
+
+/// 搜索框：先搜已加载的事件，再到 Notion 中按标题搜索未加载的事件
+#[function_component(SearchPalette)]
+pub fn search_palette(props: &SearchPaletteProps) -> Html {
+    let query = use_state(String::new);
+    let remote = use_state(Vec::<CalendarEvent>::new);
+    let remote_loading = use_state(|| false);
+    let highlighted = use_state(|| 0usize);
+    let input_ref = use_node_ref();
+    // 替换掉的 Timeout 被丢弃时自动取消
+    let pending_search = use_mut_ref(|| None::<Timeout>);
+    // 只采用最后一次搜索的结果
+    let search_generation = use_mut_ref(|| 0u32);
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+            || ()
+        });
+    }
+
+    let today = chrono::Local::now().naive_local().date();
+    let local_results = rank_events(&props.events, &query, today);
+    let remote_only = not_loaded(&props.events, &remote);
+    let remote_results = rank_events(&remote_only, &query, today);
+    let results: Vec<CalendarEvent> = local_results
+        .iter()
+        .chain(remote_results.iter())
+        .map(|e| (*e).clone())
+        .collect();
+
+    let on_input = {
+        let query = query.clone();
+        let remote = remote.clone();
+        let remote_loading = remote_loading.clone();
+        let highlighted = highlighted.clone();
+        let pending_search = pending_search.clone();
+        let search_generation = search_generation.clone();
+        let api_key = props.api_key.clone();
+        let databases = props.databases.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            query.set(value.clone());
+            highlighted.set(0);
+
+            *search_generation.borrow_mut() += 1;
+            let generation = *search_generation.borrow();
+            let text = value.trim().to_string();
+            let Some(api_key) = api_key
+                .clone()
+                .filter(|_| text.chars().count() >= REMOTE_MIN_CHARS)
+            else {
+                *pending_search.borrow_mut() = None;
+                remote.set(Vec::new());
+                remote_loading.set(false);
+                return;
+            };
+
+            remote_loading.set(true);
+            let remote = remote.clone();
+            let remote_loading = remote_loading.clone();
+            let search_generation = search_generation.clone();
+            let databases = databases.clone();
+            *pending_search.borrow_mut() = Some(Timeout::new(REMOTE_DEBOUNCE_MS, move || {
+                wasm_bindgen_futures::spawn_local(async move {
+                    let client = api::NotionClient::new(api_key);
+                    let mut found = Vec::new();
+                    for db_config in &databases {
+                        if db_config.notion_database_id.is_empty() {
+                            continue;
+                        }
+                        match api::search_events(&client, db_config, &text).await {
+                            Ok(mut events) => found.append(&mut events),
+                            Err(e) => log::warn!("在 {} 中搜索失败: {}", db_config.name, e),
+                        }
+                    }
+                    if *search_generation.borrow() == generation {
+                        remote.set(found);
+                        remote_loading.set(false);
+                    }
+                });
+            }));
+        })
+    };
+
+    let on_keydown = {
+        let highlighted = highlighted.clone();
+        let results = results.clone();
+        let on_select = props.on_select.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" => {
+                e.prevent_default();
+                if !results.is_empty() {
+                    highlighted.set((*highlighted + 1) % results.len());
+                }
+            }
+            "ArrowUp" => {
+                e.prevent_default();
+                if !results.is_empty() {
+                    highlighted.set((*highlighted + results.len() - 1) % results.len());
+                }
+            }
+            "Enter" => {
+                if let Some(event) = results.get(*highlighted) {
+                    on_select.emit(event.clone());
+                }
+            }
+            "Escape" => on_close.emit(()),
+            _ => {}
+        })
+    };
+
+    let render_result = |index: usize, event: &CalendarEvent| {
+        let database = props.databases.iter().find(|db| db.id == event.database_id);
+        let on_click = {
+            let on_select = props.on_select.clone();
+            let event = event.clone();
+            Callback::from(move |_: MouseEvent| on_select.emit(event.clone()))
+        };
+        let on_hover = {
+            let highlighted = highlighted.clone();
+            Callback::from(move |_: MouseEvent| highlighted.set(index))
+        };
+        html! {
+            <div
+                class={classes!("search-result", (*highlighted == index).then_some("highlighted"))}
+                onclick={on_click}
+                onmouseenter={on_hover}
+            >
+                <span class="event-color-dot" style={format!("background-color: {}", event.color)} />
+                <div class="search-result-body">
+                    <div class="event-title">{&event.title}</div>
+                    {match match_hint(event, &query) {
+                        Some(hint) => html! { <div class="search-hint">{hint}</div> },
+                        None => html! {},
+                    }}
+                </div>
+                <div class="search-result-meta">
+                    <span>{event_date_text(event)}</span>
+                    {match database {
+                        Some(db) => html! { <span class="search-db">{&db.name}</span> },
+                        None => html! {},
+                    }}
+                </div>
+            </div>
+        }
+    };
+
+    let has_query = !query.trim().is_empty();
+
+    html! {
+        <div class="modal-overlay search-overlay" onclick={props.on_close.reform(|_| ())}>
+            <div class="search-palette" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <input
+                    ref={input_ref}
+                    class="search-input"
+                    type="search"
+                    placeholder="搜索事件标题、属性和正文…"
+                    value={(*query).clone()}
+                    oninput={on_input}
+                    onkeydown={on_keydown}
+                />
+                <div class="search-results">
+                    {local_results.iter().enumerate().map(|(i, e)| render_result(i, e)).collect::<Html>()}
+                    {if remote_results.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <>
+                                <div class="search-section">{"Notion 中的其他结果"}</div>
+                                {remote_results.iter().enumerate().map(|(i, e)| {
+                                    render_result(local_results.len() + i, e)
+                                }).collect::<Html>()}
+                            </>
+                        }
+                    }}
+                    {if *remote_loading {
+                        html! { <div class="search-status">{"正在搜索 Notion…"}</div> }
+                    } else if has_query && results.is_empty() {
+                        html! { <div class="search-status">{"没有找到匹配的事件"}</div> }
+                    } else if !has_query {
+                        html! { <div class="search-status">{"↑↓ 选择，Enter 跳转，Esc 关闭"}</div> }
+                    } else {
+                        html! {}
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DisplaySlot, EventProperty, PropertyValue, RichText};
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    fn terms(query: &str) -> Vec<String> {
+        query.split_whitespace().map(str::to_lowercase).collect()
+    }
+
+    #[test]
+    fn match_score_table() {
+        let event = CalendarEvent {
+            description: Some("带上投影仪".to_string()),
+            properties: vec![EventProperty {
+                name: "地点".to_string(),
+                slot: DisplaySlot::Subtitle,
+                value: PropertyValue::RichText {
+                    rich_text: vec![RichText {
+                        plain_text: "三楼会议室".to_string(),
+                        href: None,
+                    }],
+                },
+            }],
+            ..CalendarEvent::fixture("Standup", day(1))
+        };
+
+        let cases = [
+            ("standup", Some(100)),
+            ("STAND", Some(60)),
+            ("dup", Some(40)),
+            ("会议室", Some(20)),
+            ("投影仪", Some(10)),
+            ("stand 投影仪", Some(70)),
+            ("stand 午饭", None),
+            ("", Some(0)),
+        ];
+        for (query, expected) in cases {
+            assert_eq!(match_score(&event, &terms(query)), expected, "{}", query);
+        }
+    }
+
+    #[test]
+    fn rank_events_orders_by_score_then_distance() {
+        let events = [
+            CalendarEvent::fixture("周会 远", day(20)),
+            CalendarEvent::fixture("例行周会", day(2)),
+            CalendarEvent::fixture("周会", day(9)),
+            CalendarEvent::fixture("周会 近", day(11)),
+            CalendarEvent::fixture("午饭", day(10)),
+        ];
+
+        let titles: Vec<&str> = rank_events(&events, "周会", day(10))
+            .into_iter()
+            .map(|e| e.title.as_str())
+            .collect();
+        assert_eq!(titles, ["周会", "周会 近", "周会 远", "例行周会"]);
+
+        assert!(rank_events(&events, "   ", day(10)).is_empty());
+    }
+
+    #[test]
+    fn not_loaded_compares_page_ids() {
+        let with_page = |id: &str, page: &str| CalendarEvent {
+            notion_page_id: Some(page.to_string()),
+            ..CalendarEvent::fixture(id, day(1))
+        };
+        let loaded = [with_page("local-a", "page-a")];
+        let remote = [
+            with_page("remote-a", "page-a"),
+            with_page("remote-b", "page-b"),
+        ];
+
+        let ids: Vec<String> = not_loaded(&loaded, &remote)
+            .into_iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, ["remote-b"]);
+    }
+}
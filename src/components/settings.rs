@@ -1,8 +1,14 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::types::{DatabaseConfig, AppState};
-use crate::utils::{is_valid_notion_key, is_valid_database_id, clean_database_id, generate_color};
+use crate::api::NotionClient;
+use crate::types::{
+    AppState, DatabaseConfig, DisplaySlot, NotionDatabase, NotionIcon, PropertyMapping,
+    PropertyType, WeekNumbering, WeekStart,
+};
+use crate::utils::{
+    clean_database_id, generate_color, is_valid_database_id, is_valid_notion_key, parse_notion_url,
+};
 
 #[derive(Properties, Clone, PartialEq)]
 pub struct SettingsPanelProps {
@@ -16,6 +22,7 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
     let state = use_state(|| props.state.clone());
     let error_message = use_state(|| None::<String>);
     let success_message = use_state(|| None::<String>);
+    let show_picker = use_state(|| false);
 
     // 更新 API Key
     let on_api_key_change = {
@@ -29,6 +36,40 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
         })
     };
 
+    // 一周的第一天，选项的值是 `WeekStart::ALL` 中的下标
+    let on_week_start_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let Some(week_start) = input
+                .value()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| WeekStart::ALL.get(i).copied())
+            else {
+                return;
+            };
+            let mut new_state = (*state).clone();
+            new_state.week_start = week_start;
+            state.set(new_state);
+        })
+    };
+
+    // 周数，空值表示不显示，其余是 `WeekNumbering::ALL` 中的下标
+    let on_week_numbers_change = {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut new_state = (*state).clone();
+            new_state.week_numbers = input
+                .value()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| WeekNumbering::ALL.get(i).copied());
+            state.set(new_state);
+        })
+    };
+
     // 添加新数据库
     let on_add_database = {
         let state = state.clone();
@@ -47,6 +88,35 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
         })
     };
 
+    // 从 Notion 选择数据库，自动填写名称和标题、日期属性
+    let on_pick_database = {
+        let state = state.clone();
+        let error_message = error_message.clone();
+        Callback::from(move |database: NotionDatabase| {
+            error_message.set(None);
+            let mut new_state = (*state).clone();
+            let first = |kind| database.property_names(kind).into_iter().next();
+            let new_db = DatabaseConfig {
+                id: uuid::Uuid::new_v4().to_string(),
+                name: Some(database.plain_title())
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| format!("数据库 {}", new_state.databases.len() + 1)),
+                notion_database_id: clean_database_id(&database.id),
+                date_property: first(PropertyType::Date).unwrap_or_default(),
+                title_property: first(PropertyType::Title).unwrap_or_default(),
+                color: generate_color(new_state.databases.len()),
+                ..Default::default()
+            };
+            new_state.databases.push(new_db);
+            state.set(new_state);
+        })
+    };
+
+    let on_toggle_picker = {
+        let show_picker = show_picker.clone();
+        Callback::from(move |_: MouseEvent| show_picker.set(!*show_picker))
+    };
+
     // 删除数据库
     let on_remove_database = {
         let state = state.clone();
@@ -79,7 +149,9 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
             // 验证 API Key
             if let Some(ref key) = state.notion_api_key {
                 if !is_valid_notion_key(key) {
-                    error_message.set(Some("Notion API Key 格式不正确（应以 'secret_' 开头）".to_string()));
+                    error_message.set(Some(
+                        "Notion API Key 格式不正确（应以 'secret_' 开头）".to_string(),
+                    ));
                     return;
                 }
             }
@@ -87,17 +159,15 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
             // 验证数据库配置
             for db in &state.databases {
                 if !db.notion_database_id.is_empty()
-                    && !is_valid_database_id(&clean_database_id(&db.notion_database_id)) {
-                    error_message.set(Some(format!(
-                        "数据库 '{}' 的 ID 格式不正确",
-                        db.name
-                    )));
+                    && !is_valid_database_id(&db.notion_database_id)
+                {
+                    error_message.set(Some(format!("数据库 '{}' 的 ID 格式不正确", db.name)));
                     return;
                 }
             }
 
             // 保存到本地存储
-            if let Err(e) = crate::utils::save_state(&*state) {
+            if let Err(e) = crate::utils::save_state(&state) {
                 error_message.set(Some(format!("保存失败: {}", e)));
                 return;
             }
@@ -146,6 +216,35 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
                         </div>
                     </section>
 
+                    // 日历显示
+                    <section class="settings-section">
+                        <h3>{"日历显示"}</h3>
+                        <div class="form-row">
+                            <div class="form-group">
+                                <label>{"每周的第一天"}</label>
+                                <select onchange={on_week_start_change}>
+                                    {WeekStart::ALL.iter().enumerate().map(|(i, week_start)| html! {
+                                        <option value={i.to_string()} selected={*week_start == state.week_start}>
+                                            {week_start.as_str()}
+                                        </option>
+                                    }).collect::<Html>()}
+                                </select>
+                            </div>
+                            <div class="form-group">
+                                <label>{"周数"}</label>
+                                <select onchange={on_week_numbers_change}>
+                                    <option value="" selected={state.week_numbers.is_none()}>{"不显示"}</option>
+                                    {WeekNumbering::ALL.iter().enumerate().map(|(i, numbering)| html! {
+                                        <option value={i.to_string()} selected={state.week_numbers == Some(*numbering)}>
+                                            {numbering.as_str()}
+                                        </option>
+                                    }).collect::<Html>()}
+                                </select>
+                                <span class="help-text">{"显示在月视图和周视图左侧"}</span>
+                            </div>
+                        </div>
+                    </section>
+
                     // 数据库配置
                     <section class="settings-section">
                         <h3>{"日历数据库"}</h3>
@@ -158,15 +257,38 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
                                     <DatabaseConfigCard
                                         index={i}
                                         db={db}
+                                        api_key={state.notion_api_key.clone()}
                                         on_update={on_update}
                                         on_remove={on_remove}
                                     />
                                 }
                             }).collect::<Html>()}
                         </div>
-                        <button class="add-db-btn" onclick={on_add_database}>
-                            {"+ 添加数据库"}
-                        </button>
+                        <div class="add-db-actions">
+                            <button class="add-db-btn" onclick={on_add_database}>
+                                {"+ 添加数据库"}
+                            </button>
+                            {match state.notion_api_key {
+                                Some(ref key) if is_valid_notion_key(key) => html! {
+                                    <button class="add-db-btn" onclick={on_toggle_picker}>
+                                        {if *show_picker { "收起" } else { "从 Notion 选择" }}
+                                    </button>
+                                },
+                                _ => html! {},
+                            }}
+                        </div>
+                        {match state.notion_api_key {
+                            Some(ref key) if *show_picker && is_valid_notion_key(key) => html! {
+                                <DatabasePicker
+                                    api_key={key.clone()}
+                                    configured_ids={state.databases.iter()
+                                        .map(|d| d.notion_database_id.clone())
+                                        .collect::<Vec<_>>()}
+                                    on_pick={on_pick_database}
+                                />
+                            },
+                            _ => html! {},
+                        }}
                     </section>
 
                     // 消息提示
@@ -189,10 +311,95 @@ pub fn settings_panel(props: &SettingsPanelProps) -> Html {
     }
 }
 
+#[derive(Properties, Clone, PartialEq)]
+struct DatabasePickerProps {
+    api_key: String,
+    /// 已配置的数据库 ID（无连字符）
+    configured_ids: Vec<String>,
+    on_pick: Callback<NotionDatabase>,
+}
+
+/// 列出共享给集成的数据库，点击即可添加
+#[function_component(DatabasePicker)]
+fn database_picker(props: &DatabasePickerProps) -> Html {
+    // None = 加载中
+    let databases = use_state(|| None::<Result<Vec<NotionDatabase>, String>>);
+
+    {
+        let databases = databases.clone();
+        use_effect_with(props.api_key.clone(), move |api_key: &String| {
+            databases.set(None);
+            let client = NotionClient::new(api_key.clone());
+            wasm_bindgen_futures::spawn_local(async move {
+                databases.set(Some(client.list_databases().await));
+            });
+            || ()
+        });
+    }
+
+    let content = match *databases {
+        None => html! { <div class="help-text">{"加载中..."}</div> },
+        Some(Err(ref error)) => html! { <div class="alert alert-error">{error}</div> },
+        Some(Ok(ref list)) if list.is_empty() => html! {
+            <div class="help-text">{"没有找到共享给集成的数据库"}</div>
+        },
+        Some(Ok(ref list)) => list
+            .iter()
+            .map(|database| {
+                let added = props
+                    .configured_ids
+                    .contains(&clean_database_id(&database.id));
+                let has_date = !database.property_names(PropertyType::Date).is_empty();
+                let on_pick = {
+                    let on_pick = props.on_pick.clone();
+                    let database = database.clone();
+                    Callback::from(move |_: MouseEvent| on_pick.emit(database.clone()))
+                };
+                let title = database.plain_title();
+                html! {
+                    <div class="picker-item">
+                        <span class="picker-icon">{database_icon(database.icon.as_ref())}</span>
+                        <span class="picker-title">
+                            {if title.is_empty() { "无标题".to_string() } else { title }}
+                        </span>
+                        {if added {
+                            html! { <span class="help-text">{"已添加"}</span> }
+                        } else if !has_date {
+                            html! { <span class="help-text">{"没有日期属性"}</span> }
+                        } else {
+                            html! {
+                                <button class="btn-secondary" onclick={on_pick}>{"添加"}</button>
+                            }
+                        }}
+                    </div>
+                }
+            })
+            .collect::<Html>(),
+    };
+
+    html! {
+        <div class="database-picker">
+            {content}
+        </div>
+    }
+}
+
+fn database_icon(icon: Option<&NotionIcon>) -> Html {
+    match icon {
+        Some(NotionIcon::Emoji { emoji }) => html! { {emoji} },
+        Some(NotionIcon::External { external: file }) | Some(NotionIcon::File { file }) => html! {
+            <img src={file.url.clone()} alt="" />
+        },
+        _ => html! { {"🗂️"} },
+    }
+}
+
 #[derive(Properties, Clone, PartialEq)]
 struct DatabaseConfigCardProps {
     index: usize,
     db: DatabaseConfig,
+    /// 用于读取数据库结构，可能是尚未保存的值
+    api_key: Option<String>,
     on_update: Callback<(usize, DatabaseConfig)>,
     on_remove: Callback<String>,
 }
@@ -200,6 +407,30 @@ struct DatabaseConfigCardProps {
 #[function_component(DatabaseConfigCard)]
 fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
     let is_expanded = use_state(|| props.index == 0);
+    // 粘贴的链接是否指定了视图
+    let pasted_view = use_state(|| false);
+    // None = 未加载（没有 API Key、ID 无效或加载中）
+    let schema = use_state(|| None::<Result<NotionDatabase, String>>);
+
+    // ID 或 API Key 变化时读取数据库结构
+    {
+        let schema = schema.clone();
+        use_effect_with(
+            (props.api_key.clone(), props.db.notion_database_id.clone()),
+            move |(api_key, database_id): &(Option<String>, String)| {
+                schema.set(None);
+                let api_key = api_key.clone().filter(|key| is_valid_notion_key(key));
+                let database_id = database_id.clone();
+                if let Some(api_key) = api_key.filter(|_| is_valid_database_id(&database_id)) {
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let client = NotionClient::new(api_key);
+                        schema.set(Some(client.get_database(&database_id).await));
+                    });
+                }
+                || ()
+            },
+        );
+    }
 
     let on_toggle = {
         let is_expanded = is_expanded.clone();
@@ -220,14 +451,25 @@ fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
         })
     };
 
+    // 接受链接或 ID；无法识别时保留原文，保存时提示格式错误
     let update_db_id = {
         let db = props.db.clone();
         let on_update = props.on_update.clone();
         let index = props.index;
+        let pasted_view = pasted_view.clone();
         Callback::from(move |e: Event| {
             let input: HtmlInputElement = e.target_unchecked_into();
             let mut new_db = db.clone();
-            new_db.notion_database_id = clean_database_id(&input.value());
+            match parse_notion_url(&input.value()) {
+                Some(ids) => {
+                    new_db.notion_database_id = ids.database_id;
+                    pasted_view.set(ids.view_id.is_some());
+                }
+                None => {
+                    new_db.notion_database_id = input.value().trim().to_string();
+                    pasted_view.set(false);
+                }
+            }
             on_update.emit((index, new_db));
         })
     };
@@ -237,6 +479,7 @@ fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
         let on_update = props.on_update.clone();
         let index = props.index;
         Callback::from(move |e: Event| {
+            // 输入框和下拉框共用
             let input: HtmlInputElement = e.target_unchecked_into();
             let mut new_db = db.clone();
             new_db.date_property = input.value();
@@ -256,6 +499,81 @@ fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
         })
     };
 
+    // 额外属性映射：(序号, 新映射)，None 表示删除
+    let update_mapping = {
+        let db = props.db.clone();
+        let on_update = props.on_update.clone();
+        let index = props.index;
+        Callback::from(move |(i, mapping): (usize, Option<PropertyMapping>)| {
+            let mut new_db = db.clone();
+            match mapping {
+                Some(mapping) => new_db.extra_properties[i] = mapping,
+                None => {
+                    new_db.extra_properties.remove(i);
+                }
+            }
+            on_update.emit((index, new_db));
+        })
+    };
+
+    let add_mapping = {
+        let db = props.db.clone();
+        let on_update = props.on_update.clone();
+        let index = props.index;
+        Callback::from(move |_: MouseEvent| {
+            let mut new_db = db.clone();
+            new_db.extra_properties.push(PropertyMapping {
+                property: String::new(),
+                slot: DisplaySlot::Badge,
+            });
+            on_update.emit((index, new_db));
+        })
+    };
+
+    let update_color_prop = {
+        let db = props.db.clone();
+        let on_update = props.on_update.clone();
+        let index = props.index;
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut new_db = db.clone();
+            let value = input.value().trim().to_string();
+            new_db.color_property = if value.is_empty() { None } else { Some(value) };
+            on_update.emit((index, new_db));
+        })
+    };
+
+    // 自定义颜色：(原选项名, 新选项名和颜色)，None 表示删除
+    let update_override = {
+        let db = props.db.clone();
+        let on_update = props.on_update.clone();
+        let index = props.index;
+        Callback::from(
+            move |(old_name, entry): (String, Option<(String, String)>)| {
+                let mut new_db = db.clone();
+                new_db.color_overrides.remove(&old_name);
+                if let Some((name, color)) = entry {
+                    new_db.color_overrides.insert(name, color);
+                }
+                on_update.emit((index, new_db));
+            },
+        )
+    };
+
+    let add_override = {
+        let db = props.db.clone();
+        let on_update = props.on_update.clone();
+        let index = props.index;
+        Callback::from(move |_: MouseEvent| {
+            let mut new_db = db.clone();
+            new_db
+                .color_overrides
+                .entry(String::new())
+                .or_insert_with(|| generate_color(db.color_overrides.len()));
+            on_update.emit((index, new_db));
+        })
+    };
+
     let on_remove = {
         let on_remove = props.on_remove.clone();
         let db_id = props.db.id.clone();
@@ -301,32 +619,198 @@ fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
                                 type="text"
                                 value={props.db.notion_database_id.clone()}
                                 onchange={update_db_id}
-                                placeholder="xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+                                placeholder="数据库链接或 32 位 ID"
                             />
                             <span class="help-text">
-                                {"从 Notion 数据库页面 URL 复制 32 位 ID"}
+                                {if !props.db.notion_database_id.is_empty()
+                                    && !is_valid_database_id(&props.db.notion_database_id) {
+                                    "无法识别数据库 ID，请粘贴数据库页面的链接"
+                                } else if *pasted_view {
+                                    "已从链接中提取数据库 ID；日历会显示数据库的全部页面，不按链接中的视图筛选"
+                                } else {
+                                    "粘贴 Notion 数据库页面的链接（包括 notion.site 公开链接）或 ID"
+                                }}
                             </span>
                         </div>
-                        <div class="form-row">
-                            <div class="form-group">
-                                <label>{"日期属性名"}</label>
-                                <input
-                                    type="text"
-                                    value={props.db.date_property.clone()}
-                                    onchange={update_date_prop}
-                                    placeholder="Date"
-                                />
-                            </div>
-                            <div class="form-group">
-                                <label>{"标题属性名"}</label>
-                                <input
-                                    type="text"
-                                    value={props.db.title_property.clone()}
-                                    onchange={update_title_prop}
-                                    placeholder="Name"
-                                />
-                            </div>
+                        {match *schema {
+                            Some(Ok(ref database)) => html! {
+                                <>
+                                    <div class="schema-info">
+                                        {"✓ Notion 数据库："}
+                                        <strong>{database.plain_title()}</strong>
+                                    </div>
+                                    <div class="form-row">
+                                        <div class="form-group">
+                                            <label>{"日期属性"}</label>
+                                            {property_select(
+                                                database.property_names(PropertyType::Date),
+                                                &props.db.date_property,
+                                                update_date_prop,
+                                            )}
+                                        </div>
+                                        <div class="form-group">
+                                            <label>{"标题属性"}</label>
+                                            {property_select(
+                                                database.property_names(PropertyType::Title),
+                                                &props.db.title_property,
+                                                update_title_prop,
+                                            )}
+                                        </div>
+                                    </div>
+                                </>
+                            },
+                            ref loaded => html! {
+                                <>
+                                    {if let Some(Err(ref error)) = *loaded {
+                                        html! { <div class="alert alert-error">{error}</div> }
+                                    } else {
+                                        html! {}
+                                    }}
+                                    <div class="form-row">
+                                        <div class="form-group">
+                                            <label>{"日期属性名"}</label>
+                                            <input
+                                                type="text"
+                                                value={props.db.date_property.clone()}
+                                                onchange={update_date_prop}
+                                                placeholder="Date"
+                                            />
+                                        </div>
+                                        <div class="form-group">
+                                            <label>{"标题属性名"}</label>
+                                            <input
+                                                type="text"
+                                                value={props.db.title_property.clone()}
+                                                onchange={update_title_prop}
+                                                placeholder="Name"
+                                            />
+                                        </div>
+                                    </div>
+                                </>
+                            },
+                        }}
+                        <div class="form-group">
+                            <label>{"额外显示的属性"}</label>
+                            {props.db.extra_properties.iter().enumerate().map(|(i, mapping)| {
+                                let on_property_change = {
+                                    let mapping = mapping.clone();
+                                    let update_mapping = update_mapping.clone();
+                                    Callback::from(move |e: Event| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        update_mapping.emit((i, Some(PropertyMapping {
+                                            property: input.value().trim().to_string(),
+                                            ..mapping.clone()
+                                        })));
+                                    })
+                                };
+                                let on_slot_change = {
+                                    let mapping = mapping.clone();
+                                    let update_mapping = update_mapping.clone();
+                                    Callback::from(move |e: Event| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        let slot = DisplaySlot::ALL
+                                            .into_iter()
+                                            .find(|s| s.as_str() == input.value())
+                                            .unwrap_or(mapping.slot);
+                                        update_mapping.emit((i, Some(PropertyMapping {
+                                            slot,
+                                            ..mapping.clone()
+                                        })));
+                                    })
+                                };
+                                let on_remove_mapping = update_mapping.reform(move |_: MouseEvent| (i, None));
+                                html! {
+                                    <div class="mapping-row">
+                                        <input
+                                            type="text"
+                                            value={mapping.property.clone()}
+                                            onchange={on_property_change}
+                                            placeholder="属性名，如 Status"
+                                        />
+                                        <select onchange={on_slot_change}>
+                                            {DisplaySlot::ALL.iter().map(|slot| html! {
+                                                <option value={slot.as_str()} selected={*slot == mapping.slot}>
+                                                    {slot.as_str()}
+                                                </option>
+                                            }).collect::<Html>()}
+                                        </select>
+                                        <button class="remove-btn" onclick={on_remove_mapping}>
+                                            {"✕"}
+                                        </button>
+                                    </div>
+                                }
+                            }).collect::<Html>()}
+                            <button class="add-db-btn" onclick={add_mapping}>
+                                {"+ 添加属性"}
+                            </button>
                         </div>
+                        <div class="form-group">
+                            <label>{"颜色属性"}</label>
+                            <input
+                                type="text"
+                                value={props.db.color_property.clone().unwrap_or_default()}
+                                onchange={update_color_prop}
+                                placeholder="Select 或 Status 属性名，如 Status"
+                            />
+                            <span class="help-text">
+                                {"留空时所有事件使用数据库颜色，否则使用选项在 Notion 中的颜色"}
+                            </span>
+                        </div>
+                        {if props.db.color_property.is_some() {
+                            html! {
+                                <div class="form-group">
+                                    <label>{"自定义选项颜色"}</label>
+                                    {props.db.color_overrides.iter().map(|(name, color)| {
+                                        let on_name_change = {
+                                            let update_override = update_override.clone();
+                                            let name = name.clone();
+                                            let color = color.clone();
+                                            Callback::from(move |e: Event| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                let new_name = input.value().trim().to_string();
+                                                update_override.emit((name.clone(), Some((new_name, color.clone()))));
+                                            })
+                                        };
+                                        let on_color_change = {
+                                            let update_override = update_override.clone();
+                                            let name = name.clone();
+                                            Callback::from(move |e: Event| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                update_override.emit((name.clone(), Some((name.clone(), input.value()))));
+                                            })
+                                        };
+                                        let on_remove_override = {
+                                            let name = name.clone();
+                                            update_override.reform(move |_: MouseEvent| (name.clone(), None))
+                                        };
+                                        html! {
+                                            <div class="mapping-row">
+                                                <input
+                                                    type="text"
+                                                    value={name.clone()}
+                                                    onchange={on_name_change}
+                                                    placeholder="选项名，如 Done"
+                                                />
+                                                <input
+                                                    type="color"
+                                                    class="color-input"
+                                                    value={color.clone()}
+                                                    onchange={on_color_change}
+                                                />
+                                                <button class="remove-btn" onclick={on_remove_override}>
+                                                    {"✕"}
+                                                </button>
+                                            </div>
+                                        }
+                                    }).collect::<Html>()}
+                                    <button class="add-db-btn" onclick={add_override}>
+                                        {"+ 添加颜色"}
+                                    </button>
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }}
                     </div>
                 }
             } else {
@@ -335,3 +819,24 @@ fn database_config_card(props: &DatabaseConfigCardProps) -> Html {
         </div>
     }
 }
+
+/// 属性下拉框；当前值不在数据库中时仍列出并标明，避免静默改掉配置
+fn property_select(names: Vec<String>, current: &str, on_change: Callback<Event>) -> Html {
+    let missing = !names.iter().any(|name| name == current);
+    html! {
+        <select onchange={on_change}>
+            {if missing {
+                html! {
+                    <option value={current.to_string()} selected={true}>
+                        {format!("{}（不存在）", current)}
+                    </option>
+                }
+            } else {
+                html! {}
+            }}
+            {names.iter().map(|name| html! {
+                <option value={name.clone()} selected={name == current}>{name}</option>
+            }).collect::<Html>()}
+        </select>
+    }
+}
@@ -0,0 +1,182 @@
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::utils::shortcuts::{filter_commands, Command, ALL_COMMANDS};
+
+fn key_hints(command: &Command) -> Html {
+    command
+        .keys()
+        .iter()
+        .map(|key| html! { <kbd>{*key}</kbd> })
+        .collect()
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct CommandPaletteProps {
+    /// 是否有选中的事件，没有时不列出编辑和删除
+    pub has_event: bool,
+    pub on_run: Callback<Command>,
+    pub on_close: Callback<()>,
+}
+
+/// 命令面板：列出所有操作，输入筛选后回车执行
+#[function_component(CommandPalette)]
+pub fn command_palette(props: &CommandPaletteProps) -> Html {
+    let query = use_state(String::new);
+    let highlighted = use_state(|| 0usize);
+    let input_ref = use_node_ref();
+
+    {
+        let input_ref = input_ref.clone();
+        use_effect_with((), move |_| {
+            if let Some(input) = input_ref.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+            || ()
+        });
+    }
+
+    let commands: Vec<Command> = filter_commands(&query)
+        .into_iter()
+        .filter(|command| props.has_event || !command.needs_event())
+        .filter(|command| *command != Command::OpenPalette)
+        .collect();
+
+    let on_input = {
+        let query = query.clone();
+        let highlighted = highlighted.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+            highlighted.set(0);
+        })
+    };
+
+    let on_keydown = {
+        let highlighted = highlighted.clone();
+        let commands = commands.clone();
+        let on_run = props.on_run.clone();
+        let on_close = props.on_close.clone();
+        Callback::from(move |e: KeyboardEvent| match e.key().as_str() {
+            "ArrowDown" => {
+                e.prevent_default();
+                if !commands.is_empty() {
+                    highlighted.set((*highlighted + 1) % commands.len());
+                }
+            }
+            "ArrowUp" => {
+                e.prevent_default();
+                if !commands.is_empty() {
+                    highlighted.set((*highlighted + commands.len() - 1) % commands.len());
+                }
+            }
+            "Enter" => {
+                if let Some(command) = commands.get(*highlighted) {
+                    on_run.emit(*command);
+                }
+            }
+            "Escape" => on_close.emit(()),
+            _ => {}
+        })
+    };
+
+    html! {
+        <div class="modal-overlay search-overlay" onclick={props.on_close.reform(|_| ())}>
+            <div class="search-palette" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <input
+                    ref={input_ref}
+                    class="search-input"
+                    type="text"
+                    placeholder="输入命令…"
+                    value={(*query).clone()}
+                    oninput={on_input}
+                    onkeydown={on_keydown}
+                />
+                <div class="search-results">
+                    {commands.iter().enumerate().map(|(index, command)| {
+                        let on_click = {
+                            let on_run = props.on_run.clone();
+                            let command = *command;
+                            Callback::from(move |_: MouseEvent| on_run.emit(command))
+                        };
+                        let on_hover = {
+                            let highlighted = highlighted.clone();
+                            Callback::from(move |_: MouseEvent| highlighted.set(index))
+                        };
+                        html! {
+                            <div
+                                class={classes!("search-result", "command-item", (*highlighted == index).then_some("highlighted"))}
+                                onclick={on_click}
+                                onmouseenter={on_hover}
+                            >
+                                <span class="command-label">{command.label()}</span>
+                                <span class="command-keys">{key_hints(command)}</span>
+                            </div>
+                        }
+                    }).collect::<Html>()}
+                    {if commands.is_empty() {
+                        html! { <div class="search-status">{"没有匹配的命令"}</div> }
+                    } else {
+                        html! {}
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct ShortcutHelpProps {
+    pub on_close: Callback<()>,
+}
+
+/// 快捷键说明，Esc 或再按一次 ? 关闭
+#[function_component(ShortcutHelp)]
+pub fn shortcut_help(props: &ShortcutHelpProps) -> Html {
+    {
+        let on_close = props.on_close.clone();
+        use_effect_with((), move |_| {
+            let listener = EventListener::new(&gloo::utils::document(), "keydown", move |e| {
+                let Some(e) = e.dyn_ref::<web_sys::KeyboardEvent>() else {
+                    return;
+                };
+                if matches!(e.key().as_str(), "Escape" | "?") {
+                    e.prevent_default();
+                    on_close.emit(());
+                }
+            });
+            move || drop(listener)
+        });
+    }
+
+    html! {
+        <div class="modal-overlay" onclick={props.on_close.reform(|_| ())}>
+            <div class="modal shortcut-help" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <div class="modal-header">
+                    <h3>{"快捷键"}</h3>
+                    <button class="close-btn" onclick={props.on_close.reform(|_| ())}>{"✕"}</button>
+                </div>
+                <div class="modal-body shortcut-list">
+                    {ALL_COMMANDS.iter().filter(|c| !c.keys().is_empty()).map(|command| html! {
+                        <div class="shortcut-row">
+                            <span>{command.label()}</span>
+                            <span class="command-keys">{key_hints(command)}</span>
+                        </div>
+                    }).collect::<Html>()}
+                    <div class="shortcut-row">
+                        <span>{"在日期格子间移动"}</span>
+                        <span class="command-keys">
+                            <kbd>{"Tab"}</kbd><kbd>{"←"}</kbd><kbd>{"↑"}</kbd><kbd>{"→"}</kbd><kbd>{"↓"}</kbd>
+                        </span>
+                    </div>
+                    <div class="shortcut-row">
+                        <span>{"在选中的日期新建事件"}</span>
+                        <span class="command-keys"><kbd>{"Enter"}</kbd></span>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,55 @@
+use yew::prelude::*;
+
+use crate::types::DatabaseConfig;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct DatabaseSidebarProps {
+    pub databases: Vec<DatabaseConfig>,
+    /// 当前显示的数据库 id
+    pub visible_ids: Vec<String>,
+    pub on_toggle: Callback<String>,
+    /// Alt + 点击：只显示这个数据库
+    pub on_solo: Callback<String>,
+}
+
+/// 数据库列表，勾选控制日历中是否显示
+#[function_component(DatabaseSidebar)]
+pub fn database_sidebar(props: &DatabaseSidebarProps) -> Html {
+    html! {
+        <aside class="sidebar">
+            <h3 class="sidebar-title">{"日历"}</h3>
+            {props.databases.iter().map(|db| {
+                let visible = props.visible_ids.contains(&db.id);
+                let onclick = {
+                    let on_toggle = props.on_toggle.clone();
+                    let on_solo = props.on_solo.clone();
+                    let id = db.id.clone();
+                    Callback::from(move |e: MouseEvent| {
+                        // 勾选状态由 visible_ids 决定，不让复选框自己切换
+                        e.prevent_default();
+                        if e.alt_key() {
+                            on_solo.emit(id.clone());
+                        } else {
+                            on_toggle.emit(id.clone());
+                        }
+                    })
+                };
+                html! {
+                    <div
+                        class={classes!("sidebar-item", (!visible).then_some("hidden"))}
+                        title="Alt + 点击只显示这个日历"
+                        {onclick}
+                    >
+                        <input
+                            type="checkbox"
+                            checked={visible}
+                            style={format!("accent-color: {}", db.color)}
+                        />
+                        <span class="db-color-dot" style={format!("background-color: {}", db.color)} />
+                        <span class="sidebar-name">{&db.name}</span>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </aside>
+    }
+}
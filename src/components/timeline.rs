@@ -0,0 +1,409 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::components::calendar::{assign_lanes, event_badges, is_draggable};
+use crate::types::{
+    group_events, groupable_properties, CalendarEvent, DatabaseConfig, GroupBy, WeekStart,
+};
+use crate::utils::month_name;
+use crate::utils::navigation::{add_months, week_start};
+
+/// 每一行的高度（像素）
+const LANE_HEIGHT: i32 = 32;
+
+/// 时间轴的缩放级别
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TimelineScale {
+    Day,
+    Week,
+    Month,
+}
+
+impl TimelineScale {
+    const ALL: [TimelineScale; 3] = [
+        TimelineScale::Day,
+        TimelineScale::Week,
+        TimelineScale::Month,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TimelineScale::Day => "日",
+            TimelineScale::Week => "周",
+            TimelineScale::Month => "月",
+        }
+    }
+
+    /// 每天的宽度（像素）
+    fn day_width(&self) -> i32 {
+        match self {
+            TimelineScale::Day => 40,
+            TimelineScale::Week => 12,
+            TimelineScale::Month => 4,
+        }
+    }
+
+    /// 包含 `date` 的可见范围（含两端），日和周刻度从一周的第一天开始
+    fn range(&self, date: NaiveDate, first_day: Weekday) -> (NaiveDate, NaiveDate) {
+        match self {
+            TimelineScale::Day => {
+                let start = week_start(date, first_day);
+                (start, start + Duration::days(27))
+            }
+            TimelineScale::Week => {
+                let start = week_start(date, first_day) - Duration::weeks(2);
+                (start, start + Duration::weeks(16) - Duration::days(1))
+            }
+            TimelineScale::Month => {
+                let start = add_months(date.with_day(1).unwrap(), -1);
+                (start, add_months(start, 12) - Duration::days(1))
+            }
+        }
+    }
+
+    /// 刻度所在的日期和标签
+    fn ticks(&self, start: NaiveDate, end: NaiveDate) -> Vec<(NaiveDate, String)> {
+        let mut ticks = Vec::new();
+        let mut date = start;
+        while date <= end {
+            match self {
+                TimelineScale::Day => {
+                    ticks.push((date, date.day().to_string()));
+                    date += Duration::days(1);
+                }
+                TimelineScale::Week => {
+                    ticks.push((date, format!("{}/{}", date.month(), date.day())));
+                    date += Duration::weeks(1);
+                }
+                TimelineScale::Month => {
+                    ticks.push((date, month_name(date.month()).to_string()));
+                    date = add_months(date, 1);
+                }
+            }
+        }
+        ticks
+    }
+}
+
+/// 拖拽的部位
+#[derive(Clone, Copy, PartialEq)]
+enum DragMode {
+    Move,
+    ResizeStart,
+    ResizeEnd,
+}
+
+/// 拖拽中的事件，`days` 为当前已经移动的天数
+#[derive(Clone, PartialEq)]
+struct TimelineDrag {
+    event: CalendarEvent,
+    mode: DragMode,
+    origin_x: i32,
+    days: i64,
+}
+
+impl TimelineDrag {
+    /// 移动 `days` 天后的开始和结束日期
+    fn dates(&self) -> (NaiveDate, Option<NaiveDate>) {
+        let event = &self.event;
+        let delta = Duration::days(self.days);
+        let end = event.end_date.unwrap_or(event.start_date);
+        match self.mode {
+            DragMode::Move => (event.start_date + delta, event.end_date.map(|d| d + delta)),
+            DragMode::ResizeStart => {
+                let start = (event.start_date + delta).min(end);
+                (start, (end != start).then_some(end))
+            }
+            DragMode::ResizeEnd => {
+                let end = (end + delta).max(event.start_date);
+                (event.start_date, (end != event.start_date).then_some(end))
+            }
+        }
+    }
+
+    /// 松开后的事件；日期没有变化时返回 None
+    fn result(&self) -> Option<CalendarEvent> {
+        let (start_date, end_date) = self.dates();
+        if start_date == self.event.start_date && end_date == self.event.end_date {
+            return None;
+        }
+        Some(CalendarEvent {
+            start_date,
+            end_date,
+            ..self.event.clone()
+        })
+    }
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TimelineViewProps {
+    pub current_date: NaiveDate,
+    pub events: Vec<CalendarEvent>,
+    pub databases: Vec<DatabaseConfig>,
+    pub on_event_click: Callback<CalendarEvent>,
+    pub on_event_change: Callback<CalendarEvent>,
+    pub on_load_range: Callback<(NaiveDate, NaiveDate, Callback<()>)>,
+    pub week_start: WeekStart,
+}
+
+/// 时间线视图：事件按开始和结束日期画成横条，可拖动改期或拖动两端调整范围
+#[function_component(TimelineView)]
+pub fn timeline_view(props: &TimelineViewProps) -> Html {
+    let scale = use_state(|| TimelineScale::Week);
+    let group_by = use_state(|| GroupBy::Database);
+    let drag = use_state(|| None::<TimelineDrag>);
+
+    let (range_start, range_end) = scale.range(props.current_date, props.week_start.weekday());
+    let day_width = scale.day_width();
+    let total_width = ((range_end - range_start).num_days() as i32 + 1) * day_width;
+
+    // 可见范围变化时加载这段时间的事件
+    {
+        let on_load_range = props.on_load_range.clone();
+        use_effect_with((range_start, range_end), move |range| {
+            on_load_range.emit((range.0, range.1, Callback::noop()));
+            || ()
+        });
+    }
+
+    let properties = groupable_properties(&props.events);
+    // 选中的属性已经不存在时退回按数据库分组
+    let effective_group = match &*group_by {
+        GroupBy::Property(name) if !properties.contains(name) => GroupBy::Database,
+        other => other.clone(),
+    };
+    let groups = group_events(&props.events, &props.databases, &effective_group);
+
+    let on_scale_click = |value: TimelineScale| {
+        let scale = scale.clone();
+        Callback::from(move |_: MouseEvent| scale.set(value))
+    };
+
+    let on_group_change = {
+        let group_by = group_by.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            group_by.set(if value.is_empty() {
+                GroupBy::Database
+            } else {
+                GroupBy::Property(value)
+            });
+        })
+    };
+
+    let on_mouse_move = {
+        let drag = drag.clone();
+        Callback::from(move |e: MouseEvent| {
+            let Some(current) = drag.as_ref() else {
+                return;
+            };
+            let days = ((e.client_x() - current.origin_x) as f64 / day_width as f64).round() as i64;
+            if days != current.days {
+                drag.set(Some(TimelineDrag {
+                    days,
+                    ..current.clone()
+                }));
+            }
+        })
+    };
+
+    let on_mouse_up = {
+        let drag = drag.clone();
+        let on_event_change = props.on_event_change.clone();
+        let on_event_click = props.on_event_click.clone();
+        Callback::from(move |_: MouseEvent| {
+            let Some(current) = drag.as_ref() else {
+                return;
+            };
+            match current.result() {
+                Some(event) => on_event_change.emit(event),
+                // 没有移动的按下松开当作点击
+                None if current.mode == DragMode::Move && current.days == 0 => {
+                    on_event_click.emit(current.event.clone())
+                }
+                None => {}
+            }
+            drag.set(None);
+        })
+    };
+
+    let on_mouse_leave = {
+        let drag = drag.clone();
+        Callback::from(move |_: MouseEvent| drag.set(None))
+    };
+
+    let start_drag = |event: &CalendarEvent, mode: DragMode| {
+        let drag = drag.clone();
+        let on_event_click = props.on_event_click.clone();
+        let event = event.clone();
+        Callback::from(move |e: MouseEvent| {
+            e.stop_propagation();
+            e.prevent_default();
+            if !is_draggable(&event) {
+                on_event_click.emit(event.clone());
+                return;
+            }
+            drag.set(Some(TimelineDrag {
+                event: event.clone(),
+                mode,
+                origin_x: e.client_x(),
+                days: 0,
+            }));
+        })
+    };
+
+    let x_of = |date: NaiveDate| (date - range_start).num_days() as i32 * day_width;
+    let ticks = scale.ticks(range_start, range_end);
+    let today = chrono::Local::now().naive_local().date();
+
+    let rows = groups
+        .iter()
+        .map(|group| {
+            // 拖拽中的事件按预览后的日期排布
+            let events: Vec<CalendarEvent> = group
+                .events
+                .iter()
+                .map(|event| match drag.as_ref() {
+                    Some(d) if d.event.id == event.id => {
+                        let (start_date, end_date) = d.dates();
+                        CalendarEvent {
+                            start_date,
+                            end_date,
+                            ..event.clone()
+                        }
+                    }
+                    _ => event.clone(),
+                })
+                .collect();
+            let laid_out = assign_lanes(&events, range_start, range_end);
+            let lanes = laid_out.iter().map(|e| e.lane + 1).max().unwrap_or(1);
+
+            let bars = laid_out
+                .iter()
+                .map(|item| {
+                    let event = item.event;
+                    let dragging = drag.as_ref().is_some_and(|d| d.event.id == event.id);
+                    let left = x_of(item.start);
+                    let width = x_of(item.end) - left + day_width;
+                    html! {
+                        <div
+                            class={classes!(
+                                "timeline-bar",
+                                dragging.then_some("dragging"),
+                                (item.start != event.start_date).then_some("continues-before"),
+                                (item.end != event.end_date.unwrap_or(event.start_date)).then_some("continues-after")
+                            )}
+                            style={format!(
+                                "left: {}px; width: {}px; top: {}px; background-color: {}",
+                                left, width, item.lane as i32 * LANE_HEIGHT, event.color
+                            )}
+                            title={event.tooltip()}
+                            tabindex="0"
+                            data-event-id={event.id.clone()}
+                            onmousedown={start_drag(event, DragMode::Move)}
+                        >
+                            {if is_draggable(event) {
+                                html! {
+                                    <span
+                                        class="timeline-handle start"
+                                        title="拖动调整开始日期"
+                                        onmousedown={start_drag(event, DragMode::ResizeStart)}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+                            <span class="timeline-bar-title">{&event.title}</span>
+                            {event_badges(event)}
+                            {if is_draggable(event) {
+                                html! {
+                                    <span
+                                        class="timeline-handle end"
+                                        title="拖动调整结束日期"
+                                        onmousedown={start_drag(event, DragMode::ResizeEnd)}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }}
+                        </div>
+                    }
+                })
+                .collect::<Html>();
+
+            html! {
+                <div class="timeline-row" style={format!("height: {}px", lanes as i32 * LANE_HEIGHT)}>
+                    <div class="timeline-group">
+                        {if let Some(color) = &group.color {
+                            html! { <span class="db-color-dot" style={format!("background-color: {}", color)} /> }
+                        } else {
+                            html! {}
+                        }}
+                        <span>{&group.name}</span>
+                    </div>
+                    <div class="timeline-track" style={format!("width: {}px", total_width)}>
+                        {bars}
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="timeline-view">
+            <div class="calendar-header">
+                <h2>{format!("{} - {}", range_start.format("%Y/%m/%d"), range_end.format("%Y/%m/%d"))}</h2>
+                <div class="timeline-controls">
+                    <select onchange={on_group_change}>
+                        <option value="" selected={effective_group == GroupBy::Database}>{"按数据库分组"}</option>
+                        {properties.iter().map(|name| html! {
+                            <option
+                                value={name.clone()}
+                                selected={effective_group == GroupBy::Property(name.clone())}
+                            >
+                                {format!("按「{}」分组", name)}
+                            </option>
+                        }).collect::<Html>()}
+                    </select>
+                    <div class="timeline-scale">
+                        {TimelineScale::ALL.iter().map(|&value| html! {
+                            <button
+                                class={classes!("view-btn", (*scale == value).then_some("active"))}
+                                onclick={on_scale_click(value)}
+                            >
+                                {value.as_str()}
+                            </button>
+                        }).collect::<Html>()}
+                    </div>
+                </div>
+            </div>
+            <div
+                class={classes!("timeline-body", drag.is_some().then_some("dragging"))}
+                onmousemove={on_mouse_move}
+                onmouseup={on_mouse_up}
+                onmouseleave={on_mouse_leave}
+            >
+                <div class="timeline-row timeline-axis">
+                    <div class="timeline-group" />
+                    <div class="timeline-track" style={format!("width: {}px", total_width)}>
+                        {ticks.into_iter().map(|(date, label)| html! {
+                            <span class="timeline-tick" style={format!("left: {}px", x_of(date))}>{label}</span>
+                        }).collect::<Html>()}
+                        {if (range_start..=range_end).contains(&today) {
+                            html! { <span class="timeline-today" style={format!("left: {}px", x_of(today))} /> }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
+                </div>
+                {if groups.is_empty() {
+                    html! { <div class="no-events">{"这段时间没有事件"}</div> }
+                } else {
+                    rows
+                }}
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,43 @@
+use gloo::timers::callback::Timeout;
+use yew::prelude::*;
+
+/// 提示自动消失的时间（毫秒）
+const TOAST_DURATION: u32 = 5000;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct UndoToastProps {
+    pub message: String,
+    pub can_undo: bool,
+    pub on_undo: Callback<()>,
+    pub on_dismiss: Callback<()>,
+}
+
+#[function_component(UndoToast)]
+pub fn undo_toast(props: &UndoToastProps) -> Html {
+    // 每条新消息重新计时，组件卸载时 Timeout 被丢弃即取消
+    {
+        let on_dismiss = props.on_dismiss.clone();
+        use_effect_with(props.message.clone(), move |_| {
+            let timeout = Timeout::new(TOAST_DURATION, move || on_dismiss.emit(()));
+            move || drop(timeout)
+        });
+    }
+
+    html! {
+        <div class="toast">
+            <span>{&props.message}</span>
+            {if props.can_undo {
+                html! {
+                    <button class="toast-action" onclick={props.on_undo.reform(|_| ())}>
+                        {"撤销"}
+                    </button>
+                }
+            } else {
+                html! {}
+            }}
+            <button class="toast-close" onclick={props.on_dismiss.reform(|_| ())}>
+                {"✕"}
+            </button>
+        </div>
+    }
+}
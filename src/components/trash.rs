@@ -0,0 +1,106 @@
+use yew::prelude::*;
+
+use crate::store::TrashEntry;
+use crate::types::DatabaseConfig;
+use crate::utils::format_date;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct TrashPanelProps {
+    pub entries: Vec<TrashEntry>,
+    pub databases: Vec<DatabaseConfig>,
+    pub on_restore: Callback<String>, // page_id
+    pub on_forget: Callback<String>,  // page_id
+    pub on_close: Callback<()>,
+}
+
+#[function_component(TrashPanel)]
+pub fn trash_panel(props: &TrashPanelProps) -> Html {
+    // 只列出仍在配置中的数据库的页面，按数据库分组
+    let groups: Vec<(&DatabaseConfig, Vec<&TrashEntry>)> = props
+        .databases
+        .iter()
+        .map(|db| {
+            let entries = props
+                .entries
+                .iter()
+                .filter(|e| e.event.database_id == db.id)
+                .collect::<Vec<_>>();
+            (db, entries)
+        })
+        .filter(|(_, entries)| !entries.is_empty())
+        .collect();
+
+    html! {
+        <div class="settings-panel-overlay" onclick={props.on_close.reform(|_| ())}>
+            <div class="settings-panel" onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}>
+                <div class="settings-header">
+                    <h2>{"🗑️ 回收站"}</h2>
+                    <button class="close-btn" onclick={props.on_close.reform(|_| ())}>
+                        {"✕"}
+                    </button>
+                </div>
+
+                <div class="settings-content">
+                    {if groups.is_empty() {
+                        html! {
+                            <div class="no-events">
+                                <span class="no-events-icon">{"☕️"}</span>
+                                <p>{"回收站是空的"}</p>
+                            </div>
+                        }
+                    } else {
+                        groups.iter().map(|(db, entries)| html! {
+                            <section class="settings-section">
+                                <h3>
+                                    <span
+                                        class="db-color-dot trash-db-dot"
+                                        style={format!("background-color: {}", db.color)}
+                                    />
+                                    {&db.name}
+                                </h3>
+                                <div class="trash-list">
+                                    {entries.iter().map(|entry| {
+                                        let page_id = entry.page_id().to_string();
+                                        let on_restore = props.on_restore.reform({
+                                            let page_id = page_id.clone();
+                                            move |_: MouseEvent| page_id.clone()
+                                        });
+                                        let on_forget = props.on_forget.reform(move |_: MouseEvent| page_id.clone());
+                                        html! {
+                                            <div class="trash-item">
+                                                <div class="event-details">
+                                                    <div class="event-title">{&entry.event.title}</div>
+                                                    <div class="event-date">
+                                                        {format!(
+                                                            "{} · 删除于 {}",
+                                                            format_date(&entry.event.start_date),
+                                                            entry.archived_at
+                                                                .with_timezone(&chrono::Local)
+                                                                .format("%m/%d %H:%M")
+                                                        )}
+                                                    </div>
+                                                </div>
+                                                <div class="trash-actions">
+                                                    <button class="btn-secondary" onclick={on_restore}>
+                                                        {"恢复"}
+                                                    </button>
+                                                    <button
+                                                        class="remove-btn"
+                                                        title="从回收站移除（页面仍保留在 Notion 的回收站中）"
+                                                        onclick={on_forget}
+                                                    >
+                                                        {"✕"}
+                                                    </button>
+                                                </div>
+                                            </div>
+                                        }
+                                    }).collect::<Html>()}
+                                </div>
+                            </section>
+                        }).collect::<Html>()
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::types::{CalendarEvent, DatabaseConfig, WeekStart};
+use crate::utils::date_grid::MonthGrid;
+use crate::utils::{month_name, weekday_name};
+
+/// 颜色最深时对应的事件数
+const MAX_SHADE_COUNT: usize = 4;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct YearViewProps {
+    pub current_date: NaiveDate,
+    pub events: Vec<CalendarEvent>,
+    pub databases: Vec<DatabaseConfig>,
+    /// 点击某天：放大到那个月
+    pub on_month_click: Callback<NaiveDate>,
+    pub on_load_range: Callback<(NaiveDate, NaiveDate, Callback<()>)>,
+    pub week_start: WeekStart,
+}
+
+/// 每天的事件数，跨天事件计入其中的每一天
+fn count_by_day(
+    events: &[CalendarEvent],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> HashMap<NaiveDate, usize> {
+    let mut counts = HashMap::new();
+    for event in events {
+        let mut date = event.start_date.max(start);
+        let last = event.end_date.unwrap_or(event.start_date).min(end);
+        while date <= last {
+            *counts.entry(date).or_insert(0) += 1;
+            date += Duration::days(1);
+        }
+    }
+    counts
+}
+
+/// 按事件数混合数据库颜色与白色
+fn shade_style(color: &str, count: usize) -> String {
+    if count == 0 {
+        return String::new();
+    }
+    let percent = 20 + 80 * count.min(MAX_SHADE_COUNT) / MAX_SHADE_COUNT;
+    format!(
+        "background-color: color-mix(in srgb, {} {}%, white)",
+        color, percent
+    )
+}
+
+/// 年视图：十二个缩略月历，按所选数据库的事件数深浅着色
+#[function_component(YearView)]
+pub fn year_view(props: &YearViewProps) -> Html {
+    // None 表示统计所有显示中的数据库
+    let selected_db = use_state(|| None::<String>);
+    let year = props.current_date.year();
+    let year_start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+    let year_end = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+
+    // 切换年份时加载全年的事件
+    {
+        let on_load_range = props.on_load_range.clone();
+        use_effect_with(year, move |_| {
+            on_load_range.emit((year_start, year_end, Callback::noop()));
+            || ()
+        });
+    }
+
+    let selected_config = selected_db
+        .as_ref()
+        .and_then(|id| props.databases.iter().find(|db| &db.id == id));
+    let color = selected_config
+        .map(|db| db.color.clone())
+        .unwrap_or_else(|| "var(--primary-color)".to_string());
+    let events: Vec<CalendarEvent> = props
+        .events
+        .iter()
+        .filter(|e| selected_config.is_none_or(|db| e.database_id == db.id))
+        .cloned()
+        .collect();
+    let counts = count_by_day(&events, year_start, year_end);
+    let today = chrono::Local::now().naive_local().date();
+
+    let on_select_db = {
+        let selected_db = selected_db.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let value = input.value();
+            selected_db.set((!value.is_empty()).then_some(value));
+        })
+    };
+
+    let months = (1..=12)
+        .map(|month| {
+            let grid = MonthGrid::new(year, month, props.week_start.weekday());
+            let on_title_click = {
+                let on_month_click = props.on_month_click.clone();
+                let first_day = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+                Callback::from(move |_: MouseEvent| on_month_click.emit(first_day))
+            };
+
+            html! {
+                <div class="mini-month">
+                    <button class="mini-month-title" onclick={on_title_click}>
+                        {month_name(month)}
+                    </button>
+                    <div class="mini-month-grid">
+                        {props.week_start.weekdays()
+                            .iter()
+                            .map(|d| html! {
+                                <div class="mini-weekday">{weekday_name(*d).trim_start_matches('周')}</div>
+                            })
+                            .collect::<Html>()}
+                        {grid.week_starts().flat_map(|week_start| {
+                            (0..7).map(move |i| week_start + Duration::days(i))
+                        }).map(|date| {
+                            if !grid.in_month(date) {
+                                return html! { <div class="mini-day other-month" /> };
+                            }
+                            let count = counts.get(&date).copied().unwrap_or(0);
+                            let on_click = {
+                                let on_month_click = props.on_month_click.clone();
+                                Callback::from(move |_: MouseEvent| on_month_click.emit(date))
+                            };
+                            html! {
+                                <div
+                                    class={classes!(
+                                        "mini-day",
+                                        (date == today).then_some("today"),
+                                        (count >= MAX_SHADE_COUNT / 2).then_some("busy")
+                                    )}
+                                    style={shade_style(&color, count)}
+                                    title={format!("{}月{}日：{} 个事件", month, date.day(), count)}
+                                    onclick={on_click}
+                                >
+                                    {date.day()}
+                                </div>
+                            }
+                        }).collect::<Html>()}
+                    </div>
+                </div>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="year-view">
+            <div class="calendar-header">
+                <h2>{format!("{} 年", year)}</h2>
+                <select class="year-db-select" onchange={on_select_db}>
+                    <option value="" selected={selected_db.is_none()}>{"全部数据库"}</option>
+                    {props.databases.iter().map(|db| html! {
+                        <option
+                            value={db.id.clone()}
+                            selected={selected_db.as_deref() == Some(db.id.as_str())}
+                        >
+                            {&db.name}
+                        </option>
+                    }).collect::<Html>()}
+                </select>
+            </div>
+            <div class="year-grid">
+                {months}
+            </div>
+        </div>
+    }
+}
@@ -1,29 +1,229 @@
-use chrono::{Duration, NaiveDate};
+use chrono::{Duration, NaiveDate, Utc};
+use gloo::events::EventListener;
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsCast;
 use yew::prelude::*;
+use yew_router::prelude::*;
 
 mod api;
 mod components;
 mod pages;
+mod store;
 mod types;
 mod utils;
 
-use components::{Calendar, EventModal, SettingsPanel};
-use types::{AppState, CalendarEvent, DatabaseConfig, ViewMode};
-use utils::load_state;
+use components::{
+    Calendar, ColorLegend, CommandPalette, DatabaseSidebar, EventModal, QuickAddBar, SearchPalette,
+    SettingsPanel, ShortcutHelp, TrashPanel, UndoToast,
+};
+use pages::Route;
+use store::{EditRecord, EventStore, EventsAction, History, HistoryAction, Trash, TrashAction};
+use types::{
+    event_properties, extract_event_from_page, AppState, CalendarEvent, DatabaseConfig, EventDraft,
+    GroupBy, PropertyMap, ViewMode,
+};
+use utils::shortcuts::{command_for_key, focus_step, Command, KeyPress};
+use utils::{
+    load_history, load_state, load_trash, navigation, save_history, save_state, save_trash,
+};
+
+/// 状态变化后延迟多久写入本地存储（毫秒）
+const SAVE_DEBOUNCE_MS: u32 = 500;
+
+/// 键盘焦点所在的日期格子或事件
+#[derive(Clone, Debug, Default, PartialEq)]
+struct FocusTarget {
+    date: Option<NaiveDate>,
+    /// 分列显示时格子所在的列，同一天在每列都有一个格子
+    column: Option<String>,
+    event_id: Option<String>,
+}
+
+fn focus_target() -> FocusTarget {
+    let Some(active) = gloo::utils::document().active_element() else {
+        return FocusTarget::default();
+    };
+    FocusTarget {
+        date: active
+            .get_attribute("data-date")
+            .and_then(|d| d.parse().ok()),
+        column: active.get_attribute("data-column"),
+        event_id: active.get_attribute("data-event-id"),
+    }
+}
+
+/// 聚焦某一天的格子，优先选同一列的；当前视图中没有这一天时返回 false
+fn focus_date_cell(date: NaiveDate, column: Option<&str>) -> bool {
+    let focus = |selector: String| {
+        gloo::utils::document()
+            .query_selector(&selector)
+            .ok()
+            .flatten()
+            .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok())
+            .is_some_and(|el| el.focus().is_ok())
+    };
+    let in_column = column.is_some_and(|column| {
+        focus(format!(
+            "[data-date=\"{}\"][data-column=\"{}\"]",
+            date, column
+        ))
+    });
+    in_column || focus(format!("[data-date=\"{}\"]", date))
+}
+
+/// 将一次编辑重新写入 Notion：本地先生效，失败时回滚，结果通过 `on_result` 报告。
+/// 撤销时传入的是反向记录，所以新建对应取消归档，删除对应归档。
+fn apply_edit(
+    api_key: String,
+    databases: &[DatabaseConfig],
+    record: EditRecord,
+    events: UseReducerHandle<EventStore>,
+    trash: UseReducerHandle<Trash>,
+    on_result: Callback<Result<(), String>>,
+) {
+    let Some(page_id) = record.page_id().map(str::to_string) else {
+        return;
+    };
+
+    // 修改时按事件所属数据库的属性名写回
+    let update_properties = match &record {
+        EditRecord::Update { before, after } => {
+            let Some(config) = databases.iter().find(|d| d.id == after.database_id) else {
+                on_result.emit(Err("找不到事件所属的数据库配置".to_string()));
+                return;
+            };
+            event_properties(Some(before), after, config)
+        }
+        _ => PropertyMap::new(),
+    };
+
+    match &record {
+        EditRecord::Create { event } => {
+            events.dispatch(EventsAction::Upsert(Box::new((**event).clone())))
+        }
+        EditRecord::Update { after, .. } => {
+            events.dispatch(EventsAction::Upsert(Box::new((**after).clone())))
+        }
+        EditRecord::Delete { .. } => events.dispatch(EventsAction::RemovePage(page_id.clone())),
+    }
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let client = api::NotionClient::new(api_key);
+
+        let result = match &record {
+            EditRecord::Create { .. } => client.restore_page(&page_id).await.map(|_| ()),
+            EditRecord::Update { .. } => client
+                .update_page(&page_id, &update_properties)
+                .await
+                .map(|_| ()),
+            EditRecord::Delete { .. } => client.delete_page(&page_id).await,
+        };
+
+        match result {
+            Ok(()) => {
+                match record {
+                    EditRecord::Create { .. } => trash.dispatch(TrashAction::Remove(page_id)),
+                    EditRecord::Delete { event } => trash.dispatch(TrashAction::Add(event)),
+                    EditRecord::Update { .. } => {}
+                }
+                on_result.emit(Ok(()));
+            }
+            Err(e) => {
+                match record {
+                    EditRecord::Create { .. } => events.dispatch(EventsAction::RemovePage(page_id)),
+                    EditRecord::Update { before, .. } => {
+                        events.dispatch(EventsAction::Upsert(Box::new(*before)))
+                    }
+                    EditRecord::Delete { event } => {
+                        events.dispatch(EventsAction::Upsert(Box::new(*event)))
+                    }
+                }
+                on_result.emit(Err(e));
+            }
+        }
+    });
+}
+
+#[function_component(Root)]
+fn root() -> Html {
+    html! {
+        <BrowserRouter>
+            <App />
+        </BrowserRouter>
+    }
+}
 
 #[function_component(App)]
 fn app() -> Html {
-    wasm_logger::init(wasm_logger::Config::default());
+    let route = use_route::<Route>().unwrap_or(Route::NotFound);
+    let navigator = use_navigator().expect("App 必须位于 BrowserRouter 内");
 
     let initial_state = load_state().unwrap_or_default();
     let state = use_state(|| initial_state);
-    let events = use_state(Vec::<CalendarEvent>::new);
-    let show_settings = use_state(|| false);
+    let events = use_reducer(EventStore::default);
     let show_event_modal = use_state(|| false);
     let selected_date = use_state(|| None::<NaiveDate>);
     let editing_event = use_state(|| None::<CalendarEvent>);
     let is_loading = use_state(|| false);
     let error_message = use_state(|| None::<String>);
+    let history = use_reducer(|| load_history().unwrap_or_default());
+    let trash = use_reducer(|| load_trash().unwrap_or_default());
+    let show_trash = use_state(|| false);
+    let show_search = use_state(|| false);
+    let show_palette = use_state(|| false);
+    let show_shortcuts = use_state(|| false);
+    // 打开命令面板时键盘焦点所在的位置，面板中的命令作用于它
+    let palette_target = use_mut_ref(FocusTarget::default);
+    let pending_focus = use_mut_ref(|| None::<(NaiveDate, Option<String>)>);
+    // (提示文字, 是否提供撤销按钮)
+    let toast = use_state(|| None::<(String, bool)>);
+
+    // 状态变化后延迟保存，连续切换日期时只写一次；关闭页面前把还没写入的状态立即保存
+    {
+        let pending_state = use_mut_ref(|| None::<AppState>);
+        let pending_save = use_mut_ref(|| None::<Timeout>);
+        let flush = {
+            let pending_state = pending_state.clone();
+            move || {
+                if let Some(state) = pending_state.borrow_mut().take() {
+                    if let Err(e) = save_state(&state) {
+                        log::warn!("保存状态失败: {}", e);
+                    }
+                }
+            }
+        };
+
+        {
+            let flush = flush.clone();
+            use_effect_with((), move |_| {
+                let listener =
+                    EventListener::new(&gloo::utils::window(), "beforeunload", move |_| flush());
+                move || drop(listener)
+            });
+        }
+
+        use_effect_with((*state).clone(), move |state: &AppState| {
+            *pending_state.borrow_mut() = Some(state.clone());
+            // 替换掉的 Timeout 被丢弃时自动取消
+            *pending_save.borrow_mut() = Some(Timeout::new(SAVE_DEBOUNCE_MS, flush));
+            || ()
+        });
+    }
+
+    // 撤销历史只在当前会话内保留
+    use_effect_with((*history).clone(), |history: &History| {
+        if let Err(e) = save_history(history) {
+            log::warn!("保存撤销历史失败: {}", e);
+        }
+        || ()
+    });
+
+    use_effect_with((*trash).clone(), |trash: &Trash| {
+        if let Err(e) = save_trash(trash) {
+            log::warn!("保存回收站失败: {}", e);
+        }
+        || ()
+    });
 
     // 刷新事件
     let refresh_events = {
@@ -46,9 +246,7 @@ fn app() -> Html {
                 is_loading.set(true);
                 error_message.set(None);
 
-                let client = api::NotionClient::new(
-                    state.notion_api_key.clone().unwrap()
-                );
+                let client = api::NotionClient::new(state.notion_api_key.clone().unwrap());
 
                 let mut all_events = Vec::new();
 
@@ -67,7 +265,7 @@ fn app() -> Html {
                     }
                 }
 
-                events.set(all_events);
+                events.dispatch(EventsAction::Reset(all_events));
                 is_loading.set(false);
             });
         })
@@ -82,61 +280,178 @@ fn app() -> Html {
         });
     }
 
-    // 视图切换
-    let on_view_change = {
+    // 日程视图滚动到新的日期范围时，从各数据库补充加载
+    let on_load_range = {
         let state = state.clone();
-        Callback::from(move |view: ViewMode| {
+        let events = events.clone();
+
+        Callback::from(
+            move |(start, end, done): (NaiveDate, NaiveDate, Callback<()>)| {
+                let Some(api_key) = state.notion_api_key.clone() else {
+                    done.emit(());
+                    return;
+                };
+                let databases = state.databases.clone();
+                let events = events.clone();
+
+                wasm_bindgen_futures::spawn_local(async move {
+                    let client = api::NotionClient::new(api_key);
+
+                    for db_config in &databases {
+                        if db_config.notion_database_id.is_empty() {
+                            continue;
+                        }
+
+                        match api::load_events_in_range(&client, db_config, start, end).await {
+                            Ok(db_events) => {
+                                for event in db_events {
+                                    events.dispatch(EventsAction::Upsert(Box::new(event)));
+                                }
+                            }
+                            Err(e) => {
+                                log::error!(
+                                    "加载数据库 {} {} 至 {} 的事件失败: {}",
+                                    db_config.name,
+                                    start,
+                                    end,
+                                    e
+                                );
+                            }
+                        }
+                    }
+                    done.emit(());
+                });
+            },
+        )
+    };
+
+    // 地址变化（前进后退、打开链接）时同步视图和日期
+    {
+        let state = state.clone();
+        let navigator = navigator.clone();
+        let show_event_modal = show_event_modal.clone();
+        let editing_event = editing_event.clone();
+        use_effect_with(route.clone(), move |route: &Route| {
+            match route {
+                Route::Home | Route::NotFound => {
+                    navigator.replace(&Route::for_view(
+                        state.current_view,
+                        state.current_date,
+                        state.week_start.weekday(),
+                    ));
+                }
+                _ => {
+                    if let Some((view, date)) =
+                        route.view_date(state.current_date, state.week_start.weekday())
+                    {
+                        if view != state.current_view || date != state.current_date {
+                            let mut new_state = (*state).clone();
+                            new_state.current_view = view;
+                            new_state.current_date = date;
+                            state.set(new_state);
+                        }
+                    }
+                }
+            }
+
+            // 离开 /event 时关闭由地址打开的编辑框
+            let opened_from_route = editing_event
+                .as_ref()
+                .is_some_and(|e| e.notion_page_id.is_some());
+            if !matches!(route, Route::Event { .. }) && opened_from_route {
+                show_event_modal.set(false);
+            }
+            || ()
+        });
+    }
+
+    // 打开 /event/<page_id>，事件加载完成后再显示编辑框
+    {
+        let show_event_modal = show_event_modal.clone();
+        let editing_event = editing_event.clone();
+        use_effect_with(
+            (route.clone(), events.events.clone()),
+            move |(route, events): &(Route, Vec<CalendarEvent>)| {
+                if let Route::Event { page_id } = route {
+                    let is_open = *show_event_modal
+                        && editing_event
+                            .as_ref()
+                            .and_then(|e| e.notion_page_id.as_deref())
+                            == Some(page_id.as_str());
+                    let event = events
+                        .iter()
+                        .find(|e| e.notion_page_id.as_deref() == Some(page_id.as_str()));
+                    if let (false, Some(event)) = (is_open, event) {
+                        editing_event.set(Some(event.clone()));
+                        show_event_modal.set(true);
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    // 切换视图或日期：更新状态并写入地址栏
+    let go_to = {
+        let state = state.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |(view, date): (ViewMode, NaiveDate)| {
             let mut new_state = (*state).clone();
             new_state.current_view = view;
+            new_state.current_date = date;
             state.set(new_state);
+            navigator.push(&Route::for_view(view, date, state.week_start.weekday()));
         })
     };
 
+    // 视图切换
+    let on_view_change = {
+        let state = state.clone();
+        let go_to = go_to.clone();
+        Callback::from(move |view: ViewMode| go_to.emit((view, state.current_date)))
+    };
+
     // 日期导航
     let on_prev = {
         let state = state.clone();
+        let go_to = go_to.clone();
         Callback::from(move |_| {
-            let mut new_state = (*state).clone();
-            new_state.current_date = match new_state.current_view {
-                ViewMode::Month => {
-                    new_state.current_date - Duration::days(30)
-                }
-                ViewMode::Week => {
-                    new_state.current_date - Duration::days(7)
-                }
-                ViewMode::Day => {
-                    new_state.current_date - Duration::days(1)
-                }
-            };
-            state.set(new_state);
+            let date = navigation::step(state.current_date, state.current_view, -1);
+            go_to.emit((state.current_view, date));
         })
     };
 
     let on_next = {
         let state = state.clone();
+        let go_to = go_to.clone();
         Callback::from(move |_| {
-            let mut new_state = (*state).clone();
-            new_state.current_date = match new_state.current_view {
-                ViewMode::Month => {
-                    new_state.current_date + Duration::days(30)
-                }
-                ViewMode::Week => {
-                    new_state.current_date + Duration::days(7)
-                }
-                ViewMode::Day => {
-                    new_state.current_date + Duration::days(1)
-                }
-            };
-            state.set(new_state);
+            let date = navigation::step(state.current_date, state.current_view, 1);
+            go_to.emit((state.current_view, date));
         })
     };
 
     let on_today = {
         let state = state.clone();
+        let go_to = go_to.clone();
         Callback::from(move |_| {
-            let mut new_state = (*state).clone();
-            new_state.current_date = chrono::Local::now().naive_local().date();
-            state.set(new_state);
+            go_to.emit((
+                state.current_view,
+                chrono::Local::now().naive_local().date(),
+            ));
+        })
+    };
+
+    // 跳转到选择的年月
+    let on_month_pick = {
+        let state = state.clone();
+        let go_to = go_to.clone();
+        Callback::from(move |e: Event| {
+            let input: web_sys::HtmlInputElement = e.target_unchecked_into();
+            let Some((year, month)) = navigation::parse_year_month(&input.value()) else {
+                return;
+            };
+            let date = navigation::jump_to_month(state.current_date, year, month);
+            go_to.emit((state.current_view, date));
         })
     };
 
@@ -152,83 +467,624 @@ fn app() -> Html {
         })
     };
 
-    // 事件点击 - 编辑
+    // 事件点击 - 编辑；已写入 Notion 的事件通过地址打开，便于分享和后退
     let on_event_click = {
         let show_event_modal = show_event_modal.clone();
         let editing_event = editing_event.clone();
-        Callback::from(move |event: CalendarEvent| {
-            editing_event.set(Some(event));
-            show_event_modal.set(true);
-        })
+        let navigator = navigator.clone();
+        Callback::from(
+            move |event: CalendarEvent| match event.notion_page_id.clone() {
+                Some(page_id) => navigator.push(&Route::Event { page_id }),
+                None => {
+                    editing_event.set(Some(event));
+                    show_event_modal.set(true);
+                }
+            },
+        )
     };
 
-    // 保存事件
+    // 保存事件：先在本地应用修改，Notion 确认后用返回的页面校正，失败则回滚
     let on_save_event = {
         let state = state.clone();
-        let refresh_events = refresh_events.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let toast = toast.clone();
         let error_message = error_message.clone();
 
-        Callback::from(move |(title, date, db_id, page_id): (String, String, String, Option<String>)| {
-            let state = state.clone();
-            let refresh_events = refresh_events.clone();
+        Callback::from(move |draft: EventDraft| {
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let Some(config) = state
+                .databases
+                .iter()
+                .find(|d| d.id == draft.database_id)
+                .cloned()
+            else {
+                return;
+            };
+
+            let page_id = draft.page_id.clone();
+            let original = page_id
+                .as_deref()
+                .and_then(|id| events.find_by_page_id(id))
+                .cloned();
+
+            let optimistic = match original {
+                Some(ref event) => CalendarEvent {
+                    title: draft.title.clone(),
+                    start_date: draft.start_date,
+                    end_date: draft.end_date,
+                    start_time: draft.start_time,
+                    end_time: draft.end_time,
+                    all_day: draft.all_day(),
+                    properties: draft.properties.clone(),
+                    updated_at: Utc::now(),
+                    ..event.clone()
+                },
+                None => CalendarEvent {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    title: draft.title.clone(),
+                    database_id: config.id.clone(),
+                    notion_page_id: None,
+                    start_date: draft.start_date,
+                    end_date: draft.end_date,
+                    start_time: draft.start_time,
+                    end_time: draft.end_time,
+                    all_day: draft.all_day(),
+                    description: None,
+                    color: config.color.clone(),
+                    color_label: None,
+                    properties: draft.properties.clone(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                },
+            };
+            events.dispatch(EventsAction::Upsert(Box::new(optimistic.clone())));
+
+            let events = events.clone();
+            let history = history.clone();
+            let toast = toast.clone();
             let error_message = error_message.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                if let Some(ref api_key) = state.notion_api_key {
-                    let client = api::NotionClient::new(api_key.clone());
+                let client = api::NotionClient::new(api_key);
 
-                    let db_config = state.databases.iter()
-                        .find(|d| d.id == db_id);
+                let properties = event_properties(original.as_ref(), &optimistic, &config);
 
-                    if let Some(config) = db_config {
-                        let result = if let Some(page_id) = page_id {
-                            client.update_page(&page_id, Some(&title), Some(&date)).await
-                        } else {
-                            client.create_page(&config.notion_database_id, &title, &date).await
+                let result = if let Some(page_id) = page_id {
+                    client.update_page(&page_id, &properties).await
+                } else {
+                    client
+                        .create_page(&config.notion_database_id, &properties)
+                        .await
+                };
+
+                match result {
+                    Ok(page) => {
+                        // 保留本地 id，避免视图中的条目被当作新元素重建
+                        let confirmed = match extract_event_from_page(&page, &config) {
+                            Some(event) => CalendarEvent {
+                                id: optimistic.id.clone(),
+                                ..event
+                            },
+                            None => CalendarEvent {
+                                notion_page_id: Some(page.id.clone()),
+                                ..optimistic
+                            },
                         };
+                        events.dispatch(EventsAction::Upsert(Box::new(confirmed.clone())));
 
-                        match result {
-                            Ok(_) => {
-                                refresh_events.emit(());
-                            }
-                            Err(e) => {
-                                error_message.set(Some(format!("保存失败: {}", e)));
-                            }
+                        let record = match original {
+                            Some(before) => EditRecord::Update {
+                                before: Box::new(before),
+                                after: Box::new(confirmed),
+                            },
+                            None => EditRecord::Create {
+                                event: Box::new(confirmed),
+                            },
+                        };
+                        toast.set(Some((format!("已{}", record.label()), true)));
+                        history.dispatch(HistoryAction::Record(Box::new(record)));
+                    }
+                    Err(e) => {
+                        match original {
+                            Some(event) => events.dispatch(EventsAction::Upsert(Box::new(event))),
+                            None => events.dispatch(EventsAction::Remove(optimistic.id)),
                         }
+                        error_message.set(Some(format!("保存失败: {}", e)));
                     }
                 }
             });
         })
     };
 
-    // 删除事件
+    // 删除事件：先从本地移除，失败则恢复
     let on_delete_event = {
         let state = state.clone();
-        let refresh_events = refresh_events.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let trash = trash.clone();
+        let toast = toast.clone();
         let error_message = error_message.clone();
 
         Callback::from(move |page_id: String| {
-            let state = state.clone();
-            let refresh_events = refresh_events.clone();
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+
+            let original = events.find_by_page_id(&page_id).cloned();
+            if let Some(ref event) = original {
+                events.dispatch(EventsAction::Remove(event.id.clone()));
+            }
+
+            let events = events.clone();
+            let history = history.clone();
+            let trash = trash.clone();
+            let toast = toast.clone();
             let error_message = error_message.clone();
 
             wasm_bindgen_futures::spawn_local(async move {
-                if let Some(ref api_key) = state.notion_api_key {
-                    let client = api::NotionClient::new(api_key.clone());
+                let client = api::NotionClient::new(api_key);
 
-                    match client.delete_page(&page_id).await {
-                        Ok(_) => {
-                            refresh_events.emit(());
+                match client.delete_page(&page_id).await {
+                    Ok(_) => {
+                        if let Some(event) = original {
+                            trash.dispatch(TrashAction::Add(Box::new(event.clone())));
+                            let record = EditRecord::Delete {
+                                event: Box::new(event),
+                            };
+                            toast.set(Some((format!("已{}", record.label()), true)));
+                            history.dispatch(HistoryAction::Record(Box::new(record)));
                         }
-                        Err(e) => {
-                            error_message.set(Some(format!("删除失败: {}", e)));
+                    }
+                    Err(e) => {
+                        if let Some(event) = original {
+                            events.dispatch(EventsAction::Upsert(Box::new(event)));
+                        }
+                        error_message.set(Some(format!("删除失败: {}", e)));
+                    }
+                }
+            });
+        })
+    };
+
+    // 拖拽改期：写回 Notion，失败时 apply_edit 会回滚
+    let on_event_change = {
+        let state = state.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let trash = trash.clone();
+        let toast = toast.clone();
+        let error_message = error_message.clone();
+
+        Callback::from(move |after: CalendarEvent| {
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let Some(before) = after
+                .notion_page_id
+                .as_deref()
+                .and_then(|id| events.find_by_page_id(id))
+                .cloned()
+            else {
+                return;
+            };
+
+            let record = EditRecord::Update {
+                before: Box::new(before),
+                after: Box::new(CalendarEvent {
+                    updated_at: Utc::now(),
+                    ..after
+                }),
+            };
+            let on_result = {
+                let history = history.clone();
+                let toast = toast.clone();
+                let error_message = error_message.clone();
+                let record = record.clone();
+                Callback::from(move |result: Result<(), String>| match result {
+                    Ok(()) => {
+                        toast.set(Some((format!("已{}", record.label()), true)));
+                        history.dispatch(HistoryAction::Record(Box::new(record.clone())));
+                    }
+                    Err(e) => error_message.set(Some(format!("移动失败: {}", e))),
+                })
+            };
+            apply_edit(
+                api_key,
+                &state.databases,
+                record,
+                events.clone(),
+                trash.clone(),
+                on_result,
+            );
+        })
+    };
+
+    // 缓存页面正文，供搜索等功能使用
+    let on_description_change = {
+        let events = events.clone();
+        Callback::from(move |(page_id, text): (String, String)| {
+            let Some(event) = events.find_by_page_id(&page_id) else {
+                return;
+            };
+            let description = (!text.is_empty()).then_some(text);
+            if event.description != description {
+                events.dispatch(EventsAction::Upsert(Box::new(CalendarEvent {
+                    description,
+                    ..event.clone()
+                })));
+            }
+        })
+    };
+
+    // 撤销
+    let on_undo = {
+        let state = state.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let trash = trash.clone();
+        let toast = toast.clone();
+        let error_message = error_message.clone();
+
+        Callback::from(move |_: ()| {
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let Some(record) = history.undo.last().cloned() else {
+                return;
+            };
+
+            history.dispatch(HistoryAction::Undone);
+            toast.set(Some((format!("已撤销{}", record.label()), false)));
+
+            let on_result = {
+                let history = history.clone();
+                let error_message = error_message.clone();
+                Callback::from(move |result: Result<(), String>| {
+                    if let Err(e) = result {
+                        history.dispatch(HistoryAction::Redone);
+                        error_message.set(Some(format!("撤销失败: {}", e)));
+                    }
+                })
+            };
+            apply_edit(
+                api_key,
+                &state.databases,
+                record.inverse(),
+                events.clone(),
+                trash.clone(),
+                on_result,
+            );
+        })
+    };
+
+    // 重做
+    let on_redo = {
+        let state = state.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let trash = trash.clone();
+        let toast = toast.clone();
+        let error_message = error_message.clone();
+
+        Callback::from(move |_: ()| {
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let Some(record) = history.redo.last().cloned() else {
+                return;
+            };
+
+            history.dispatch(HistoryAction::Redone);
+            toast.set(Some((format!("已重做{}", record.label()), true)));
+
+            let on_result = {
+                let history = history.clone();
+                let error_message = error_message.clone();
+                Callback::from(move |result: Result<(), String>| {
+                    if let Err(e) = result {
+                        history.dispatch(HistoryAction::Undone);
+                        error_message.set(Some(format!("重做失败: {}", e)));
+                    }
+                })
+            };
+            apply_edit(
+                api_key,
+                &state.databases,
+                record,
+                events.clone(),
+                trash.clone(),
+                on_result,
+            );
+        })
+    };
+
+    // 从回收站恢复：取消归档，并记入撤销历史
+    let on_restore = {
+        let state = state.clone();
+        let events = events.clone();
+        let history = history.clone();
+        let trash = trash.clone();
+        let toast = toast.clone();
+        let error_message = error_message.clone();
+
+        Callback::from(move |page_id: String| {
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let Some(entry) = trash.find(&page_id).cloned() else {
+                return;
+            };
+
+            let record = EditRecord::Create {
+                event: Box::new(entry.event.clone()),
+            };
+            let on_result = {
+                let history = history.clone();
+                let toast = toast.clone();
+                let error_message = error_message.clone();
+                let record = record.clone();
+                Callback::from(move |result: Result<(), String>| match result {
+                    Ok(()) => {
+                        toast.set(Some((format!("已恢复「{}」", entry.event.title), true)));
+                        history.dispatch(HistoryAction::Record(Box::new(record.clone())));
+                    }
+                    Err(e) => error_message.set(Some(format!("恢复失败: {}", e))),
+                })
+            };
+            apply_edit(
+                api_key,
+                &state.databases,
+                record,
+                events.clone(),
+                trash.clone(),
+                on_result,
+            );
+        })
+    };
+
+    // 仅从本地回收站移除，页面仍留在 Notion 的回收站里
+    let on_forget = {
+        let trash = trash.clone();
+        Callback::from(move |page_id: String| trash.dispatch(TrashAction::Remove(page_id)))
+    };
+
+    // 打开回收站时向 Notion 核对：已在别处恢复的页面不再列出
+    let on_open_trash = {
+        let state = state.clone();
+        let trash = trash.clone();
+        let show_trash = show_trash.clone();
+
+        Callback::from(move |()| {
+            show_trash.set(true);
+
+            let Some(api_key) = state.notion_api_key.clone() else {
+                return;
+            };
+            let page_ids: Vec<String> = trash
+                .entries
+                .iter()
+                .map(|e| e.page_id().to_string())
+                .collect();
+            let trash = trash.clone();
+
+            wasm_bindgen_futures::spawn_local(async move {
+                let client = api::NotionClient::new(api_key);
+
+                for page_id in page_ids {
+                    match client.get_page(&page_id).await {
+                        Ok(page) if !page.archived => trash.dispatch(TrashAction::Remove(page_id)),
+                        Ok(_) => {}
+                        Err(e) => log::warn!("检查页面 {} 失败: {}", page_id, e),
+                    }
+                }
+            });
+        })
+    };
+
+    let on_close_trash = {
+        let show_trash = show_trash.clone();
+        Callback::from(move |_| show_trash.set(false))
+    };
+
+    let on_open_search = {
+        let show_search = show_search.clone();
+        Callback::from(move |_| show_search.set(true))
+    };
+
+    let on_close_search = {
+        let show_search = show_search.clone();
+        Callback::from(move |_| show_search.set(false))
+    };
+
+    // 跳到搜索结果所在的那一天；Notion 中搜到的未加载事件一并放入列表
+    let on_search_select = {
+        let events = events.clone();
+        let show_search = show_search.clone();
+        let go_to = go_to.clone();
+        Callback::from(move |event: CalendarEvent| {
+            show_search.set(false);
+            let date = event.start_date;
+            events.dispatch(EventsAction::Upsert(Box::new(event)));
+            go_to.emit((ViewMode::Day, date));
+        })
+    };
+
+    // 执行命令；`target` 是执行时键盘焦点所在的日期格子或事件
+    let run_command = {
+        let state = state.clone();
+        let events = events.clone();
+        let go_to = go_to.clone();
+        let on_date_click = on_date_click.clone();
+        let on_event_click = on_event_click.clone();
+        let on_delete_event = on_delete_event.clone();
+        let on_undo = on_undo.clone();
+        let on_redo = on_redo.clone();
+        let refresh_events = refresh_events.clone();
+        let show_search = show_search.clone();
+        let show_palette = show_palette.clone();
+        let show_shortcuts = show_shortcuts.clone();
+        let on_open_trash = on_open_trash.clone();
+        let palette_target = palette_target.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |(command, target): (Command, FocusTarget)| {
+            let focused_event = target
+                .event_id
+                .as_deref()
+                .and_then(|id| events.events.iter().find(|e| e.id == id))
+                .cloned();
+            match command {
+                Command::Today => go_to.emit((
+                    state.current_view,
+                    chrono::Local::now().naive_local().date(),
+                )),
+                Command::Prev | Command::Next => {
+                    let steps = if command == Command::Prev { -1 } else { 1 };
+                    let date = navigation::step(state.current_date, state.current_view, steps);
+                    go_to.emit((state.current_view, date));
+                }
+                Command::View(view) => {
+                    go_to.emit((view, target.date.unwrap_or(state.current_date)))
+                }
+                Command::NewEvent => on_date_click.emit(
+                    target
+                        .date
+                        .or(focused_event.map(|e| e.start_date))
+                        .unwrap_or(state.current_date),
+                ),
+                Command::EditEvent => {
+                    if let Some(event) = focused_event {
+                        on_event_click.emit(event);
+                    }
+                }
+                Command::DeleteEvent => {
+                    if let Some(page_id) = focused_event.and_then(|e| e.notion_page_id) {
+                        on_delete_event.emit(page_id);
+                    }
+                }
+                Command::Search => show_search.set(true),
+                Command::OpenPalette => {
+                    *palette_target.borrow_mut() = target;
+                    show_palette.set(true);
+                }
+                Command::ShowShortcuts => show_shortcuts.set(true),
+                Command::Undo => on_undo.emit(()),
+                Command::Redo => on_redo.emit(()),
+                Command::Refresh => refresh_events.emit(()),
+                Command::OpenTrash => on_open_trash.emit(()),
+                Command::OpenSettings => navigator.push(&Route::Settings),
+            }
+        })
+    };
+
+    // 方向键移到当前视图之外的日期时，翻页后再聚焦那一格
+    {
+        let pending_focus = pending_focus.clone();
+        use_effect_with(state.current_date, move |_| {
+            if let Some((date, column)) = pending_focus.borrow_mut().take() {
+                focus_date_cell(date, column.as_deref());
+            }
+            || ()
+        });
+    }
+
+    // 全局快捷键；输入框内只响应 Ctrl+K，保留浏览器自带的撤销，弹窗打开时只响应 Ctrl 组合键
+    {
+        let run_command = run_command.clone();
+        let state = state.clone();
+        let go_to = go_to.clone();
+        let pending_focus = pending_focus.clone();
+        let overlay_open = *show_event_modal
+            || *show_search
+            || *show_palette
+            || *show_shortcuts
+            || *show_trash
+            || route == Route::Settings;
+        use_effect(move || {
+            let listener = EventListener::new(&gloo::utils::document(), "keydown", move |e| {
+                let Some(e) = e.dyn_ref::<web_sys::KeyboardEvent>() else {
+                    return;
+                };
+                let press = KeyPress {
+                    key: e.key(),
+                    ctrl: e.ctrl_key() || e.meta_key(),
+                    shift: e.shift_key(),
+                    alt: e.alt_key(),
+                };
+                let in_text_field = e
+                    .target()
+                    .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                    .map(|el| matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+                    .unwrap_or(false);
+                if (in_text_field || overlay_open) && !press.ctrl {
+                    return;
+                }
+
+                let target = focus_target();
+                let on_cell = target.date.is_some() && target.event_id.is_none();
+                // 在日期格子上：方向键移动焦点，回车新建事件
+                if on_cell && !press.ctrl && !press.shift && !press.alt {
+                    if let (Some(days), Some(date)) = (focus_step(&press.key), target.date) {
+                        e.prevent_default();
+                        let next = date + Duration::days(days);
+                        if !focus_date_cell(next, target.column.as_deref()) {
+                            *pending_focus.borrow_mut() = Some((next, target.column.clone()));
+                            go_to.emit((state.current_view, next));
                         }
+                        return;
+                    }
+                    if press.key == "Enter" {
+                        e.prevent_default();
+                        run_command.emit((Command::NewEvent, target));
+                        return;
                     }
                 }
+                let Some(command) = command_for_key(&press) else {
+                    return;
+                };
+                if in_text_field && command != Command::Search {
+                    return;
+                }
+                // 没有选中事件时，回车和删除键留给浏览器
+                if command.needs_event() && target.event_id.is_none() {
+                    return;
+                }
+
+                e.prevent_default();
+                run_command.emit((command, target));
             });
+            move || drop(listener)
+        });
+    }
+
+    let on_open_palette = {
+        let run_command = run_command.clone();
+        Callback::from(move |_| run_command.emit((Command::OpenPalette, FocusTarget::default())))
+    };
+
+    let on_palette_run = {
+        let run_command = run_command.clone();
+        let show_palette = show_palette.clone();
+        let palette_target = palette_target.clone();
+        Callback::from(move |command: Command| {
+            show_palette.set(false);
+            let target = palette_target.borrow().clone();
+            run_command.emit((command, target));
         })
     };
 
+    let on_close_palette = {
+        let show_palette = show_palette.clone();
+        Callback::from(move |_| show_palette.set(false))
+    };
+
+    let on_close_shortcuts = {
+        let show_shortcuts = show_shortcuts.clone();
+        Callback::from(move |_| show_shortcuts.set(false))
+    };
+
     // 保存设置
     let on_settings_save = {
         let state = state.clone();
@@ -239,19 +1095,73 @@ fn app() -> Html {
         })
     };
 
+    // 侧栏切换数据库显示，不重新请求
+    let on_toggle_database = {
+        let state = state.clone();
+        Callback::from(move |id: String| {
+            let mut new_state = (*state).clone();
+            new_state.toggle_database(&id);
+            state.set(new_state);
+        })
+    };
+
+    let on_resource_columns_change = {
+        let state = state.clone();
+        Callback::from(move |group_by: Option<GroupBy>| {
+            let mut new_state = (*state).clone();
+            new_state.resource_columns = group_by;
+            state.set(new_state);
+        })
+    };
+
+    let on_solo_database = {
+        let state = state.clone();
+        Callback::from(move |id: String| {
+            let mut new_state = (*state).clone();
+            new_state.solo_database(&id);
+            state.set(new_state);
+        })
+    };
+
+    let visible_events: Vec<CalendarEvent> = events
+        .events
+        .iter()
+        .filter(|e| state.is_database_visible(&e.database_id))
+        .cloned()
+        .collect();
+
     let on_close_settings = {
-        let show_settings = show_settings.clone();
-        Callback::from(move |_| show_settings.set(false))
+        let state = state.clone();
+        let navigator = navigator.clone();
+        Callback::from(move |_| {
+            navigator.push(&Route::for_view(
+                state.current_view,
+                state.current_date,
+                state.week_start.weekday(),
+            ))
+        })
     };
 
     let on_open_settings = {
-        let show_settings = show_settings.clone();
-        Callback::from(move |_| show_settings.set(true))
+        let navigator = navigator.clone();
+        Callback::from(move |_| navigator.push(&Route::Settings))
     };
 
     let on_close_event_modal = {
         let show_event_modal = show_event_modal.clone();
-        Callback::from(move |_| show_event_modal.set(false))
+        let state = state.clone();
+        let navigator = navigator.clone();
+        let route = route.clone();
+        Callback::from(move |_| {
+            show_event_modal.set(false);
+            if matches!(route, Route::Event { .. }) {
+                navigator.push(&Route::for_view(
+                    state.current_view,
+                    state.current_date,
+                    state.week_start.weekday(),
+                ));
+            }
+        })
     };
 
     let on_close_error = {
@@ -259,6 +1169,20 @@ fn app() -> Html {
         Callback::from(move |_| error_message.set(None))
     };
 
+    let on_dismiss_toast = {
+        let toast = toast.clone();
+        Callback::from(move |_| toast.set(None))
+    };
+
+    let on_toast_undo = {
+        let on_undo = on_undo.clone();
+        let toast = toast.clone();
+        Callback::from(move |_| {
+            toast.set(None);
+            on_undo.emit(());
+        })
+    };
+
     html! {
         <div class="app">
             <header class="app-header">
@@ -277,30 +1201,55 @@ fn app() -> Html {
                     <button class="nav-btn" onclick={on_next}>
                         {"▶"}
                     </button>
+                    <input
+                        type="month"
+                        class="month-picker"
+                        title="跳转到月份"
+                        value={state.current_date.format("%Y-%m").to_string()}
+                        onchange={on_month_pick}
+                    />
                 </div>
 
                 <div class="view-controls">
+                    {ViewMode::ALL.iter().map(|&view| html! {
+                        <button
+                            class={classes!("view-btn", (state.current_view == view).then_some("active"))}
+                            onclick={on_view_change.reform(move |_| view)}
+                        >
+                            {view.as_str()}
+                        </button>
+                    }).collect::<Html>()}
+                </div>
+
+                <ColorLegend events={visible_events.clone()} />
+
+                <div class="header-actions">
                     <button
-                        class={classes!("view-btn", (state.current_view == ViewMode::Month).then_some("active"))}
-                        onclick={on_view_change.reform(|_| ViewMode::Month)}
+                        class="header-btn"
+                        title="搜索 (Ctrl+K)"
+                        onclick={on_open_search}
                     >
-                        {"月"}
+                        {"🔍"}
                     </button>
                     <button
-                        class={classes!("view-btn", (state.current_view == ViewMode::Week).then_some("active"))}
-                        onclick={on_view_change.reform(|_| ViewMode::Week)}
+                        class="header-btn"
+                        title="命令面板 (Ctrl+P)，快捷键说明 (?)"
+                        onclick={on_open_palette}
                     >
-                        {"周"}
+                        {"⌨️"}
                     </button>
                     <button
-                        class={classes!("view-btn", (state.current_view == ViewMode::Day).then_some("active"))}
-                        onclick={on_view_change.reform(|_| ViewMode::Day)}
+                        class="header-btn"
+                        title="回收站"
+                        onclick={on_open_trash.reform(|_| ())}
                     >
-                        {"日"}
+                        {"🗑️"}
+                        {if trash.entries.is_empty() {
+                            html! {}
+                        } else {
+                            html! { <span class="header-badge">{trash.entries.len()}</span> }
+                        }}
                     </button>
-                </div>
-
-                <div class="header-actions">
                     <button
                         class="settings-btn"
                         onclick={on_open_settings}
@@ -335,16 +1284,54 @@ fn app() -> Html {
                     html! {}
                 }}
 
-                <Calendar
-                    current_date={state.current_date}
-                    view_mode={state.current_view}
-                    events={(*events).clone()}
-                    on_date_click={on_date_click}
-                    on_event_click={on_event_click}
-                />
+                {if state.notion_api_key.is_some() {
+                    html! {
+                        <QuickAddBar
+                            databases={state.databases.clone()}
+                            on_save={on_save_event.clone()}
+                        />
+                    }
+                } else {
+                    html! {}
+                }}
+
+                <div class="main-layout">
+                    {if state.databases.len() > 1 {
+                        html! {
+                            <DatabaseSidebar
+                                databases={state.databases.clone()}
+                                visible_ids={state.databases.iter()
+                                    .filter(|d| state.is_database_visible(&d.id))
+                                    .map(|d| d.id.clone())
+                                    .collect::<Vec<_>>()}
+                                on_toggle={on_toggle_database}
+                                on_solo={on_solo_database}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }}
+                    <div class="calendar-area">
+                        <Calendar
+                            current_date={state.current_date}
+                            view_mode={state.current_view}
+                            events={visible_events}
+                            databases={state.databases.clone()}
+                            on_date_click={on_date_click}
+                            on_event_click={on_event_click}
+                            on_navigate={go_to.clone()}
+                            on_event_change={on_event_change}
+                            on_load_range={on_load_range}
+                            resource_columns={state.resource_columns.clone()}
+                            on_resource_columns_change={on_resource_columns_change}
+                            week_start={state.week_start}
+                            week_numbers={state.week_numbers}
+                        />
+                    </div>
+                </div>
             </main>
 
-            {if *show_settings {
+            {if route == Route::Settings {
                 html! {
                     <SettingsPanel
                         state={(*state).clone()}
@@ -356,6 +1343,65 @@ fn app() -> Html {
                 html! {}
             }}
 
+            {if *show_search {
+                html! {
+                    <SearchPalette
+                        events={events.events.clone()}
+                        databases={state.databases.clone()}
+                        api_key={state.notion_api_key.clone()}
+                        on_select={on_search_select}
+                        on_close={on_close_search}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+
+            {if *show_palette {
+                html! {
+                    <CommandPalette
+                        has_event={palette_target.borrow().event_id.is_some()}
+                        on_run={on_palette_run}
+                        on_close={on_close_palette}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+
+            {if *show_shortcuts {
+                html! { <ShortcutHelp on_close={on_close_shortcuts} /> }
+            } else {
+                html! {}
+            }}
+
+            {if *show_trash {
+                html! {
+                    <TrashPanel
+                        entries={trash.entries.clone()}
+                        databases={state.databases.clone()}
+                        on_restore={on_restore}
+                        on_forget={on_forget}
+                        on_close={on_close_trash}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+
+            {if let Some((ref message, can_undo)) = *toast {
+                html! {
+                    <UndoToast
+                        message={message.clone()}
+                        can_undo={can_undo && !history.undo.is_empty()}
+                        on_undo={on_toast_undo}
+                        on_dismiss={on_dismiss_toast}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+
             {if *show_event_modal {
                 html! {
                     <EventModal
@@ -365,6 +1411,8 @@ fn app() -> Html {
                         on_save={on_save_event}
                         on_delete={on_delete_event}
                         on_close={on_close_event_modal}
+                        api_key={state.notion_api_key.clone()}
+                        on_description_change={on_description_change}
                     />
                 }
             } else {
@@ -375,5 +1423,6 @@ fn app() -> Html {
 }
 
 fn main() {
-    yew::Renderer::<App>::new().render();
+    wasm_logger::init(wasm_logger::Config::default());
+    yew::Renderer::<Root>::new().render();
 }
@@ -1,2 +1,103 @@
-// 页面组件模块
-// 可以在这里添加更多页面如 About、Help 等
+// 页面路由
+use chrono::{Datelike, NaiveDate, Weekday};
+use yew_router::prelude::*;
+
+use crate::types::ViewMode;
+use crate::utils::navigation::{parse_year_month, week_start};
+
+/// 地址栏中的页面，日期均为 YYYY-MM-DD，月份为 YYYY-MM，年份为 YYYY
+#[derive(Clone, Debug, PartialEq, Routable)]
+pub enum Route {
+    #[at("/")]
+    Home,
+    #[at("/year/:year")]
+    Year { year: String },
+    #[at("/month/:month")]
+    Month { month: String },
+    #[at("/week/:date")]
+    Week { date: String },
+    #[at("/day/:date")]
+    Day { date: String },
+    #[at("/agenda/:date")]
+    Agenda { date: String },
+    #[at("/timeline/:date")]
+    Timeline { date: String },
+    #[at("/event/:page_id")]
+    Event { page_id: String },
+    #[at("/settings")]
+    Settings,
+    #[not_found]
+    #[at("/404")]
+    NotFound,
+}
+
+impl Route {
+    /// 视图和日期对应的地址；周视图使用这一周的第一天，一周从 `first_day` 开始
+    pub fn for_view(view: ViewMode, date: NaiveDate, first_day: Weekday) -> Self {
+        match view {
+            ViewMode::Year => Route::Year {
+                year: date.year().to_string(),
+            },
+            ViewMode::Month => Route::Month {
+                month: date.format("%Y-%m").to_string(),
+            },
+            ViewMode::Week => Route::Week {
+                date: week_start(date, first_day).to_string(),
+            },
+            ViewMode::Day => Route::Day {
+                date: date.to_string(),
+            },
+            ViewMode::Agenda => Route::Agenda {
+                date: date.to_string(),
+            },
+            ViewMode::Timeline => Route::Timeline {
+                date: date.to_string(),
+            },
+        }
+    }
+
+    /// 地址对应的视图和日期，不是日历页面或日期无效时为 None
+    ///
+    /// 当前日期已经在地址指定的年份、月份或那一周内时沿用当前日期，
+    /// 这样在月视图中翻页不会把日期重置到 1 号。
+    pub fn view_date(
+        &self,
+        current: NaiveDate,
+        first_day: Weekday,
+    ) -> Option<(ViewMode, NaiveDate)> {
+        let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+        match self {
+            Route::Year { year } => {
+                let year: i32 = year.parse().ok()?;
+                let date = if current.year() == year {
+                    current
+                } else {
+                    NaiveDate::from_ymd_opt(year, 1, 1)?
+                };
+                Some((ViewMode::Year, date))
+            }
+            Route::Month { month } => {
+                let (year, month) = parse_year_month(month)?;
+                let date = if current.year() == year && current.month() == month {
+                    current
+                } else {
+                    NaiveDate::from_ymd_opt(year, month, 1)?
+                };
+                Some((ViewMode::Month, date))
+            }
+            Route::Week { date } => {
+                let date = parse(date)?;
+                let date = if week_start(current, first_day) == week_start(date, first_day) {
+                    current
+                } else {
+                    date
+                };
+                Some((ViewMode::Week, date))
+            }
+            Route::Day { date } => Some((ViewMode::Day, parse(date)?)),
+            Route::Agenda { date } => Some((ViewMode::Agenda, parse(date)?)),
+            Route::Timeline { date } => Some((ViewMode::Timeline, parse(date)?)),
+            _ => None,
+        }
+    }
+}
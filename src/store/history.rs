@@ -0,0 +1,210 @@
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::types::CalendarEvent;
+
+/// 最多保留的撤销步数
+const MAX_HISTORY: usize = 50;
+
+/// 一次已经写入 Notion 的编辑
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EditRecord {
+    Create {
+        event: Box<CalendarEvent>,
+    },
+    Update {
+        before: Box<CalendarEvent>,
+        after: Box<CalendarEvent>,
+    },
+    Delete {
+        event: Box<CalendarEvent>,
+    },
+}
+
+impl EditRecord {
+    /// 反向操作：撤销新建即归档，撤销删除即取消归档
+    pub fn inverse(&self) -> EditRecord {
+        match self {
+            EditRecord::Create { event } => EditRecord::Delete {
+                event: event.clone(),
+            },
+            EditRecord::Delete { event } => EditRecord::Create {
+                event: event.clone(),
+            },
+            EditRecord::Update { before, after } => EditRecord::Update {
+                before: after.clone(),
+                after: before.clone(),
+            },
+        }
+    }
+
+    /// 编辑涉及的 Notion 页面
+    pub fn page_id(&self) -> Option<&str> {
+        match self {
+            EditRecord::Create { event } | EditRecord::Delete { event } => {
+                event.notion_page_id.as_deref()
+            }
+            EditRecord::Update { after, .. } => after.notion_page_id.as_deref(),
+        }
+    }
+
+    /// 用于提示的简短描述
+    pub fn label(&self) -> String {
+        match self {
+            EditRecord::Create { event } => format!("新建「{}」", event.title),
+            EditRecord::Delete { event } => format!("删除「{}」", event.title),
+            EditRecord::Update { before, after } => {
+                if before.title == after.title
+                    && (before.start_date != after.start_date || before.end_date != after.end_date)
+                {
+                    format!("移动「{}」", after.title)
+                } else {
+                    format!("修改「{}」", after.title)
+                }
+            }
+        }
+    }
+}
+
+/// 撤销 / 重做栈
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub undo: Vec<EditRecord>,
+    pub redo: Vec<EditRecord>,
+}
+
+pub enum HistoryAction {
+    /// 记录一次新的编辑，清空重做栈
+    Record(Box<EditRecord>),
+    /// 将撤销栈顶移到重做栈
+    Undone,
+    /// 将重做栈顶移回撤销栈
+    Redone,
+}
+
+impl Reducible for History {
+    type Action = HistoryAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut history = (*self).clone();
+
+        match action {
+            HistoryAction::Record(record) => {
+                history.undo.push(*record);
+                if history.undo.len() > MAX_HISTORY {
+                    history.undo.remove(0);
+                }
+                history.redo.clear();
+            }
+            HistoryAction::Undone => {
+                if let Some(record) = history.undo.pop() {
+                    history.redo.push(record);
+                }
+            }
+            HistoryAction::Redone => {
+                if let Some(record) = history.redo.pop() {
+                    history.undo.push(record);
+                }
+            }
+        }
+
+        Rc::new(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn event(id: &str, day: u32) -> Box<CalendarEvent> {
+        Box::new(CalendarEvent::fixture(
+            id,
+            NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+        ))
+    }
+
+    fn create(id: &str) -> EditRecord {
+        EditRecord::Create {
+            event: event(id, 1),
+        }
+    }
+
+    fn reduce(history: History, action: HistoryAction) -> History {
+        (*Rc::new(history).reduce(action)).clone()
+    }
+
+    fn record(history: History, record: EditRecord) -> History {
+        reduce(history, HistoryAction::Record(Box::new(record)))
+    }
+
+    #[test]
+    fn inverse_swaps_each_variant() {
+        assert_eq!(
+            create("a").inverse(),
+            EditRecord::Delete {
+                event: event("a", 1)
+            }
+        );
+        assert_eq!(
+            EditRecord::Delete {
+                event: event("a", 1)
+            }
+            .inverse(),
+            create("a")
+        );
+        let update = EditRecord::Update {
+            before: event("a", 1),
+            after: event("a", 2),
+        };
+        assert_eq!(
+            update.inverse(),
+            EditRecord::Update {
+                before: event("a", 2),
+                after: event("a", 1),
+            }
+        );
+        assert_eq!(update.inverse().inverse(), update);
+    }
+
+    #[test]
+    fn undo_and_redo_round_trip() {
+        let history = record(record(History::default(), create("a")), create("b"));
+
+        let history = reduce(history, HistoryAction::Undone);
+        assert_eq!(history.undo, vec![create("a")]);
+        assert_eq!(history.redo, vec![create("b")]);
+
+        let history = reduce(history, HistoryAction::Redone);
+        assert_eq!(history.undo, vec![create("a"), create("b")]);
+        assert!(history.redo.is_empty());
+
+        // 栈为空时什么也不做
+        let empty = reduce(History::default(), HistoryAction::Undone);
+        assert_eq!(reduce(empty, HistoryAction::Redone), History::default());
+    }
+
+    #[test]
+    fn recording_clears_redo() {
+        let history = record(History::default(), create("a"));
+        let history = reduce(history, HistoryAction::Undone);
+        assert_eq!(history.redo.len(), 1);
+
+        let history = record(history, create("b"));
+        assert_eq!(history.undo, vec![create("b")]);
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_beyond_limit() {
+        let history = (0..=MAX_HISTORY).fold(History::default(), |history, i| {
+            record(history, create(&i.to_string()))
+        });
+        assert_eq!(history.undo.len(), MAX_HISTORY);
+        assert_eq!(history.undo[0], create("1"));
+        assert_eq!(history.undo.last(), Some(&create(&MAX_HISTORY.to_string())));
+    }
+}
@@ -0,0 +1,138 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::types::CalendarEvent;
+
+mod history;
+mod trash;
+
+pub use history::{EditRecord, History, HistoryAction};
+pub use trash::{Trash, TrashAction, TrashEntry};
+
+/// 本地事件存储
+///
+/// 用 reducer 而不是 `use_state` 保存事件列表：异步请求完成时 dispatch 的动作
+/// 总是作用在最新的列表上，不会用旧快照覆盖期间发生的其他修改。
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventStore {
+    pub events: Vec<CalendarEvent>,
+}
+
+pub enum EventsAction {
+    /// 整体替换（全量刷新）
+    Reset(Vec<CalendarEvent>),
+    /// 按本地 id（或 Notion 页面 id）插入或替换
+    Upsert(Box<CalendarEvent>),
+    /// 按本地 id 移除
+    Remove(String),
+    /// 按 Notion 页面 id 移除
+    RemovePage(String),
+}
+
+impl Reducible for EventStore {
+    type Action = EventsAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut events = self.events.clone();
+
+        match action {
+            EventsAction::Reset(new_events) => events = new_events,
+            EventsAction::Upsert(event) => {
+                // 全量刷新会重新生成本地 id，所以同一页面也视为同一事件
+                let existing = events.iter_mut().find(|e| {
+                    e.id == event.id
+                        || (event.notion_page_id.is_some()
+                            && e.notion_page_id == event.notion_page_id)
+                });
+                match existing {
+                    Some(existing) => {
+                        *existing = CalendarEvent {
+                            id: existing.id.clone(),
+                            ..*event
+                        }
+                    }
+                    None => events.push(*event),
+                }
+            }
+            EventsAction::Remove(id) => events.retain(|e| e.id != id),
+            EventsAction::RemovePage(page_id) => {
+                events.retain(|e| e.notion_page_id.as_deref() != Some(page_id.as_str()))
+            }
+        }
+
+        Rc::new(Self { events })
+    }
+}
+
+impl EventStore {
+    pub fn find_by_page_id(&self, page_id: &str) -> Option<&CalendarEvent> {
+        self.events
+            .iter()
+            .find(|e| e.notion_page_id.as_deref() == Some(page_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn page(id: &str, page_id: &str, title: &str) -> CalendarEvent {
+        CalendarEvent {
+            title: title.to_string(),
+            notion_page_id: Some(page_id.to_string()),
+            ..CalendarEvent::fixture(id, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap())
+        }
+    }
+
+    fn reduce(store: EventStore, action: EventsAction) -> EventStore {
+        (*Rc::new(store).reduce(action)).clone()
+    }
+
+    #[test]
+    fn upsert_by_page_id_keeps_local_id() {
+        let store = EventStore {
+            events: vec![page("local", "p1", "旧标题"), page("other", "p2", "其他")],
+        };
+
+        // 刷新后同一页面带着新的本地 id 回来
+        let store = reduce(
+            store,
+            EventsAction::Upsert(Box::new(page("fresh", "p1", "新标题"))),
+        );
+        assert_eq!(store.events.len(), 2);
+        assert_eq!(store.events[0].id, "local");
+        assert_eq!(store.events[0].title, "新标题");
+        assert_eq!(store.events[1], page("other", "p2", "其他"));
+    }
+
+    #[test]
+    fn upsert_by_local_id_or_appends() {
+        let draft = CalendarEvent::fixture("local", NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        let store = reduce(
+            EventStore::default(),
+            EventsAction::Upsert(Box::new(draft.clone())),
+        );
+        assert_eq!(store.events, vec![draft]);
+
+        // Notion 确认后补上页面 id，仍是同一个事件
+        let store = reduce(
+            store,
+            EventsAction::Upsert(Box::new(page("local", "p1", "local"))),
+        );
+        assert_eq!(store.events, vec![page("local", "p1", "local")]);
+    }
+
+    #[test]
+    fn removes_by_local_or_page_id() {
+        let store = EventStore {
+            events: vec![page("a", "p1", "a"), page("b", "p2", "b")],
+        };
+        let store = reduce(store, EventsAction::Remove("a".to_string()));
+        assert_eq!(store.events, vec![page("b", "p2", "b")]);
+        let store = reduce(store, EventsAction::RemovePage("p2".to_string()));
+        assert!(store.events.is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::types::CalendarEvent;
+
+/// 一个由本应用归档的页面
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub event: CalendarEvent,
+    pub archived_at: DateTime<Utc>,
+}
+
+impl TrashEntry {
+    pub fn page_id(&self) -> &str {
+        self.event.notion_page_id.as_deref().unwrap_or_default()
+    }
+}
+
+/// 回收站
+///
+/// Notion 的数据库查询不会返回已归档页面，所以只能在归档时本地记下来。
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Trash {
+    pub entries: Vec<TrashEntry>,
+}
+
+pub enum TrashAction {
+    /// 记录一个刚归档的页面
+    Add(Box<CalendarEvent>),
+    /// 按页面 id 移除（已恢复或不再关心）
+    Remove(String),
+}
+
+impl Reducible for Trash {
+    type Action = TrashAction;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut entries = self.entries.clone();
+
+        match action {
+            TrashAction::Add(event) => {
+                if event.notion_page_id.is_none() {
+                    return self;
+                }
+                entries.retain(|e| e.event.notion_page_id != event.notion_page_id);
+                entries.insert(
+                    0,
+                    TrashEntry {
+                        event: *event,
+                        archived_at: Utc::now(),
+                    },
+                );
+            }
+            TrashAction::Remove(page_id) => entries.retain(|e| e.page_id() != page_id),
+        }
+
+        Rc::new(Self { entries })
+    }
+}
+
+impl Trash {
+    pub fn find(&self, page_id: &str) -> Option<&TrashEntry> {
+        self.entries.iter().find(|e| e.page_id() == page_id)
+    }
+}
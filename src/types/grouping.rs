@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+
+use super::{CalendarEvent, DatabaseConfig, PropertyType};
+
+/// 没有值的事件所在分组的名称
+pub const NO_VALUE_GROUP: &str = "（无）";
+
+/// 事件的分组方式
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GroupBy {
+    Database,
+    /// 按某个 select / status / people 属性的值分组
+    Property(String),
+}
+
+/// 一个分组：名称、颜色和其中的事件
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventGroup {
+    pub name: String,
+    /// 按数据库分组时为数据库颜色
+    pub color: Option<String>,
+    pub events: Vec<CalendarEvent>,
+}
+
+/// 按分组方式拆分事件
+///
+/// 按数据库分组时保持数据库的配置顺序并省略没有事件的数据库；按属性分组时
+/// 按名称排序，没有值的事件放在最后一组，people 属性中有多个人的事件在每个人的组中各出现一次。
+pub fn group_events(
+    events: &[CalendarEvent],
+    databases: &[DatabaseConfig],
+    group_by: &GroupBy,
+) -> Vec<EventGroup> {
+    match group_by {
+        GroupBy::Database => databases
+            .iter()
+            .map(|db| EventGroup {
+                name: db.name.clone(),
+                color: Some(db.color.clone()),
+                events: events
+                    .iter()
+                    .filter(|e| e.database_id == db.id)
+                    .cloned()
+                    .collect(),
+            })
+            .filter(|group| !group.events.is_empty())
+            .collect(),
+        GroupBy::Property(property) => {
+            let mut groups: Vec<EventGroup> = Vec::new();
+            for event in events {
+                let mut names = event
+                    .properties
+                    .iter()
+                    .find(|p| &p.name == property)
+                    .map(|p| p.value.group_names())
+                    .unwrap_or_default();
+                if names.is_empty() {
+                    names.push(NO_VALUE_GROUP.to_string());
+                }
+                for name in names {
+                    match groups.iter_mut().find(|g| g.name == name) {
+                        Some(group) => group.events.push(event.clone()),
+                        None => groups.push(EventGroup {
+                            name,
+                            color: None,
+                            events: vec![event.clone()],
+                        }),
+                    }
+                }
+            }
+            groups.sort_by(|a, b| {
+                (a.name == NO_VALUE_GROUP, &a.name).cmp(&(b.name == NO_VALUE_GROUP, &b.name))
+            });
+            groups
+        }
+    }
+}
+
+/// 可用于分组的属性：事件中出现过的 select / status / people 属性
+pub fn groupable_properties(events: &[CalendarEvent]) -> Vec<String> {
+    let mut names: Vec<String> = events
+        .iter()
+        .flat_map(|e| e.properties.iter())
+        .filter(|p| {
+            matches!(
+                p.value.property_type(),
+                PropertyType::Select | PropertyType::Status | PropertyType::People
+            )
+        })
+        .map(|p| p.name.clone())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::types::{DisplaySlot, EventProperty, NotionUser, PropertyValue, SelectOption};
+
+    fn event(id: &str, database_id: &str, properties: Vec<(&str, PropertyValue)>) -> CalendarEvent {
+        CalendarEvent {
+            database_id: database_id.to_string(),
+            properties: properties
+                .into_iter()
+                .map(|(name, value)| EventProperty {
+                    name: name.to_string(),
+                    slot: DisplaySlot::Badge,
+                    value,
+                })
+                .collect(),
+            ..CalendarEvent::fixture(id, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+        }
+    }
+
+    fn status(name: &str) -> PropertyValue {
+        PropertyValue::Status {
+            status: Some(SelectOption {
+                id: None,
+                name: name.to_string(),
+                color: None,
+            }),
+        }
+    }
+
+    fn people(names: &[&str]) -> PropertyValue {
+        PropertyValue::People {
+            people: names
+                .iter()
+                .map(|name| NotionUser {
+                    id: format!("{}-id", name),
+                    name: Some(name.to_string()),
+                })
+                .collect(),
+        }
+    }
+
+    fn database(id: &str) -> DatabaseConfig {
+        DatabaseConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            ..DatabaseConfig::default()
+        }
+    }
+
+    /// 每组的 (名称, 事件 id)
+    fn summary(groups: &[EventGroup]) -> Vec<(&str, Vec<&str>)> {
+        groups
+            .iter()
+            .map(|g| {
+                (
+                    g.name.as_str(),
+                    g.events.iter().map(|e| e.id.as_str()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_by_database_in_config_order() {
+        let events = vec![event("a", "db2", vec![]), event("b", "db1", vec![])];
+        let databases = vec![database("db1"), database("empty"), database("db2")];
+
+        let groups = group_events(&events, &databases, &GroupBy::Database);
+        assert_eq!(
+            summary(&groups),
+            vec![("db1", vec!["b"]), ("db2", vec!["a"])]
+        );
+        assert_eq!(groups[0].color, Some(databases[0].color.clone()));
+    }
+
+    #[test]
+    fn groups_by_property_sorted_with_no_value_last() {
+        let events = vec![
+            event("a", "db", vec![("状态", status("进行中"))]),
+            event("b", "db", vec![]),
+            event("c", "db", vec![("状态", status("完成"))]),
+            event(
+                "d",
+                "db",
+                vec![("状态", PropertyValue::Status { status: None })],
+            ),
+            event("e", "db", vec![("状态", status("进行中"))]),
+        ];
+
+        let groups = group_events(&events, &[], &GroupBy::Property("状态".to_string()));
+        assert_eq!(
+            summary(&groups),
+            vec![
+                ("完成", vec!["c"]),
+                ("进行中", vec!["a", "e"]),
+                (NO_VALUE_GROUP, vec!["b", "d"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn people_fan_out_to_each_person() {
+        let events = vec![
+            event("a", "db", vec![("负责人", people(&["乙", "甲"]))]),
+            event("b", "db", vec![("负责人", people(&["甲"]))]),
+            event("c", "db", vec![("负责人", people(&[]))]),
+        ];
+
+        let groups = group_events(&events, &[], &GroupBy::Property("负责人".to_string()));
+        assert_eq!(
+            summary(&groups),
+            vec![
+                ("乙", vec!["a"]),
+                ("甲", vec!["a", "b"]),
+                (NO_VALUE_GROUP, vec!["c"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_groupable_properties_once() {
+        let events = vec![
+            event(
+                "a",
+                "db",
+                vec![
+                    ("状态", status("完成")),
+                    ("负责人", people(&["甲"])),
+                    ("备注", PropertyValue::RichText { rich_text: vec![] }),
+                ],
+            ),
+            event(
+                "b",
+                "db",
+                vec![
+                    ("状态", status("进行中")),
+                    ("分类", PropertyValue::Select { select: None }),
+                ],
+            ),
+        ];
+
+        assert_eq!(
+            groupable_properties(&events),
+            vec!["分类", "状态", "负责人"]
+        );
+    }
+}
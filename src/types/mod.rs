@@ -1,7 +1,20 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, SecondsFormat, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+mod grouping;
+mod property;
+mod week;
+
+pub use grouping::{group_events, groupable_properties, EventGroup, GroupBy};
+pub use property::{
+    deserialize_properties, plain_rich_text, properties_request, DateValue, NotionUser,
+    PropertyMap, PropertySchema, PropertyType, PropertyValue, RichText, SelectOption,
+};
+pub use week::{WeekNumbering, WeekStart};
+
 /// Notion 数据库配置
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -11,6 +24,60 @@ pub struct DatabaseConfig {
     pub date_property: String,
     pub title_property: String,
     pub color: String,
+    /// 除标题和日期外，额外显示在事件上的属性
+    #[serde(default)]
+    pub extra_properties: Vec<PropertyMapping>,
+    /// 决定事件颜色的 select / status 属性，None 时统一用 `color`
+    #[serde(default)]
+    pub color_property: Option<String>,
+    /// 自定义的 选项名 -> 颜色，优先于 Notion 的选项颜色
+    #[serde(default)]
+    pub color_overrides: BTreeMap<String, String>,
+}
+
+impl DatabaseConfig {
+    /// 按颜色属性的取值决定事件颜色，返回 (选项名, 颜色)
+    pub fn event_color(&self, properties: &PropertyMap) -> (Option<String>, String) {
+        let option = self
+            .color_property
+            .as_ref()
+            .and_then(|name| properties.get(name))
+            .and_then(PropertyValue::selected_option);
+
+        let Some(option) = option else {
+            return (None, self.color.clone());
+        };
+
+        let color = self
+            .color_overrides
+            .get(&option.name)
+            .cloned()
+            .or_else(|| {
+                option
+                    .color
+                    .as_deref()
+                    .and_then(notion_color)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| self.color.clone());
+        (Some(option.name.clone()), color)
+    }
+}
+
+/// Notion 选项颜色名对应的显示颜色，default 沿用数据库颜色
+pub fn notion_color(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "gray" => "#9b9a97",
+        "brown" => "#a27763",
+        "orange" => "#f5a623",
+        "yellow" => "#e5b400",
+        "green" => "#4dab9a",
+        "blue" => "#529cca",
+        "purple" => "#9a6dd7",
+        "pink" => "#e255a1",
+        "red" => "#ff7369",
+        _ => return None,
+    })
 }
 
 impl Default for DatabaseConfig {
@@ -22,10 +89,55 @@ impl Default for DatabaseConfig {
             date_property: "Date".to_string(),
             title_property: "Name".to_string(),
             color: "#667eea".to_string(),
+            extra_properties: Vec::new(),
+            color_property: None,
+            color_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// 额外属性在事件上的显示位置
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplaySlot {
+    /// 标题旁的小标签
+    Badge,
+    /// 标题下方的一行文字
+    Subtitle,
+    /// 只在鼠标悬停时显示
+    Tooltip,
+}
+
+impl DisplaySlot {
+    pub const ALL: [DisplaySlot; 3] = [
+        DisplaySlot::Badge,
+        DisplaySlot::Subtitle,
+        DisplaySlot::Tooltip,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplaySlot::Badge => "徽标",
+            DisplaySlot::Subtitle => "副标题",
+            DisplaySlot::Tooltip => "悬停提示",
         }
     }
 }
 
+/// 额外属性的映射：Notion 属性名 -> 显示位置
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PropertyMapping {
+    pub property: String,
+    pub slot: DisplaySlot,
+}
+
+/// 事件上一个额外属性的值
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventProperty {
+    pub name: String,
+    pub slot: DisplaySlot,
+    pub value: PropertyValue,
+}
+
 /// 日历事件
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CalendarEvent {
@@ -35,27 +147,138 @@ pub struct CalendarEvent {
     pub notion_page_id: Option<String>,
     pub start_date: NaiveDate,
     pub end_date: Option<NaiveDate>,
+    /// 非全天事件的开始时间（本地时间）
+    #[serde(default)]
+    pub start_time: Option<NaiveTime>,
+    /// 非全天事件的结束时间（本地时间）
+    #[serde(default)]
+    pub end_time: Option<NaiveTime>,
     pub all_day: bool,
     pub description: Option<String>,
     pub color: String,
+    /// 颜色来自颜色属性时的选项名，用于图例
+    #[serde(default)]
+    pub color_label: Option<String>,
+    /// 按数据库配置映射的额外属性
+    #[serde(default)]
+    pub properties: Vec<EventProperty>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl CalendarEvent {
+    /// 写入 Notion 的开始日期
+    pub fn notion_start(&self) -> String {
+        format_notion_date(self.start_date, self.start_time)
+    }
+
+    /// 写入 Notion 的结束日期，没有结束日期时为 None
+    pub fn notion_end(&self) -> Option<String> {
+        self.end_date
+            .map(|date| format_notion_date(date, self.end_time.filter(|_| !self.all_day)))
+    }
+
+    /// 指定位置上非空的属性文本
+    pub fn slot_texts(&self, slot: DisplaySlot) -> Vec<String> {
+        self.properties
+            .iter()
+            .filter(|p| p.slot == slot)
+            .map(|p| p.value.plain_text())
+            .filter(|text| !text.is_empty())
+            .collect()
+    }
+
+    /// 悬停提示：标题加上所有非空属性，每行一个
+    pub fn tooltip(&self) -> String {
+        std::iter::once(self.title.clone())
+            .chain(
+                self.properties
+                    .iter()
+                    .map(|p| (p, p.value.plain_text()))
+                    .filter(|(_, text)| !text.is_empty())
+                    .map(|(p, text)| format!("{}: {}", p.name, text)),
+            )
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+impl CalendarEvent {
+    /// 测试用的全天事件，标题与 id 相同，其余字段按需用结构体更新语法覆盖
+    pub fn fixture(id: &str, start_date: NaiveDate) -> Self {
+        Self {
+            id: id.to_string(),
+            title: id.to_string(),
+            database_id: "db".to_string(),
+            notion_page_id: None,
+            start_date,
+            end_date: None,
+            start_time: None,
+            end_time: None,
+            all_day: true,
+            description: None,
+            color: "#000".to_string(),
+            color_label: None,
+            properties: Vec::new(),
+            created_at: DateTime::UNIX_EPOCH,
+            updated_at: DateTime::UNIX_EPOCH,
+        }
+    }
+}
+
+/// 事件编辑表单提交的内容
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventDraft {
+    pub title: String,
+    pub database_id: String,
+    pub page_id: Option<String>, // None = 新建
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub properties: Vec<EventProperty>,
+}
+
+impl EventDraft {
+    pub fn all_day(&self) -> bool {
+        self.start_time.is_none()
+    }
+}
+
 /// 日历视图模式
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ViewMode {
     Month,
     Week,
     Day,
+    /// 按天分组的事件列表
+    Agenda,
+    /// 十二个月的缩略月历
+    Year,
+    /// 按开始和结束日期画成横条
+    Timeline,
 }
 
 impl ViewMode {
+    /// 视图切换按钮的顺序
+    pub const ALL: [ViewMode; 6] = [
+        ViewMode::Year,
+        ViewMode::Month,
+        ViewMode::Week,
+        ViewMode::Day,
+        ViewMode::Agenda,
+        ViewMode::Timeline,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ViewMode::Month => "月",
             ViewMode::Week => "周",
             ViewMode::Day => "日",
+            ViewMode::Agenda => "日程",
+            ViewMode::Year => "年",
+            ViewMode::Timeline => "时间线",
         }
     }
 }
@@ -68,6 +291,14 @@ pub struct AppState {
     pub current_view: ViewMode,
     pub current_date: NaiveDate,
     pub selected_database_ids: Vec<String>,
+    /// 周视图和日视图按数据库或属性分列显示；None 时所有事件排在一起
+    #[serde(default)]
+    pub resource_columns: Option<GroupBy>,
+    #[serde(default)]
+    pub week_start: WeekStart,
+    /// 月视图和周视图左侧显示的周数；None 时不显示
+    #[serde(default)]
+    pub week_numbers: Option<WeekNumbering>,
 }
 
 impl Default for AppState {
@@ -78,29 +309,245 @@ impl Default for AppState {
             current_view: ViewMode::Month,
             current_date: chrono::Local::now().naive_local().date(),
             selected_database_ids: vec![],
+            resource_columns: None,
+            week_start: WeekStart::default(),
+            week_numbers: None,
+        }
+    }
+}
+
+impl AppState {
+    /// 数据库是否显示；`selected_database_ids` 中没有有效的 id 时全部显示
+    pub fn is_database_visible(&self, id: &str) -> bool {
+        let any_selected = self
+            .databases
+            .iter()
+            .any(|d| self.selected_database_ids.contains(&d.id));
+        !any_selected || self.selected_database_ids.iter().any(|s| s == id)
+    }
+
+    /// 切换数据库的显示，至少保留一个
+    pub fn toggle_database(&mut self, id: &str) {
+        let mut visible: Vec<String> = self
+            .databases
+            .iter()
+            .filter(|d| self.is_database_visible(&d.id))
+            .map(|d| d.id.clone())
+            .collect();
+
+        if visible.iter().any(|v| v == id) {
+            if visible.len() > 1 {
+                visible.retain(|v| v != id);
+            }
+        } else {
+            visible.push(id.to_string());
+        }
+        self.set_visible_databases(visible);
+    }
+
+    /// 只显示这个数据库；它已经是唯一显示的数据库时恢复全部显示
+    pub fn solo_database(&mut self, id: &str) {
+        let is_solo = self
+            .databases
+            .iter()
+            .all(|d| (d.id == id) == self.is_database_visible(&d.id));
+        if is_solo {
+            self.set_visible_databases(Vec::new());
+        } else {
+            self.set_visible_databases(vec![id.to_string()]);
         }
     }
+
+    /// 全部显示时保存为空列表，之后新增的数据库默认显示
+    fn set_visible_databases(&mut self, visible: Vec<String>) {
+        self.selected_database_ids = if visible.len() >= self.databases.len() {
+            Vec::new()
+        } else {
+            visible
+        };
+    }
 }
 
 /// Notion API 响应类型
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct NotionDatabase {
     pub id: String,
-    pub title: Vec<NotionText>,
-    pub properties: serde_json::Value,
+    pub title: Vec<RichText>,
+    #[serde(default)]
+    pub icon: Option<NotionIcon>,
+    pub properties: BTreeMap<String, PropertySchema>,
+}
+
+/// 页面或数据库的图标
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotionIcon {
+    Emoji {
+        emoji: String,
+    },
+    External {
+        external: NotionFile,
+    },
+    File {
+        file: NotionFile,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct NotionFile {
+    pub url: String,
+}
+
+impl NotionDatabase {
+    pub fn plain_title(&self) -> String {
+        self.title.iter().map(|t| t.plain_text.as_str()).collect()
+    }
+
+    /// 指定类型的所有属性名
+    pub fn property_names(&self, kind: PropertyType) -> Vec<String> {
+        self.properties
+            .values()
+            .filter(|p| p.kind == kind)
+            .map(|p| p.name.clone())
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct NotionPage {
     pub id: String,
-    pub properties: serde_json::Value,
+    #[serde(deserialize_with = "deserialize_properties")]
+    pub properties: PropertyMap,
     pub created_time: String,
     pub last_edited_time: String,
+    #[serde(default)]
+    pub archived: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct NotionText {
-    pub plain_text: String,
+/// Notion 块，只细分日历里需要预览的几种
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotionBlock {
+    pub id: String,
+    pub content: BlockContent,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockContent {
+    /// `plain` 表示正文只有一段无格式、无链接的文字，整段改写不会丢失信息
+    Paragraph {
+        text: String,
+        plain: bool,
+    },
+    Heading {
+        level: u8,
+        text: String,
+    },
+    ToDo {
+        text: String,
+        checked: bool,
+    },
+    BulletedListItem(String),
+    NumberedListItem(String),
+    /// 其他类型只保留类型名
+    Unsupported(String),
+}
+
+impl NotionBlock {
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        let id = value.get("id")?.as_str()?.to_string();
+        let kind = value.get("type")?.as_str()?;
+        let body = value.get(kind);
+        let text = body
+            .and_then(|b| b.get("rich_text"))
+            .map(rich_text_to_plain)
+            .unwrap_or_default();
+
+        let content = match kind {
+            "paragraph" => BlockContent::Paragraph {
+                text,
+                plain: body
+                    .and_then(|b| b.get("rich_text"))
+                    .is_none_or(is_plain_rich_text),
+            },
+            "heading_1" => BlockContent::Heading { level: 1, text },
+            "heading_2" => BlockContent::Heading { level: 2, text },
+            "heading_3" => BlockContent::Heading { level: 3, text },
+            "to_do" => BlockContent::ToDo {
+                text,
+                checked: body
+                    .and_then(|b| b.get("checked"))
+                    .and_then(|c| c.as_bool())
+                    .unwrap_or(false),
+            },
+            "bulleted_list_item" => BlockContent::BulletedListItem(text),
+            "numbered_list_item" => BlockContent::NumberedListItem(text),
+            other => BlockContent::Unsupported(other.to_string()),
+        };
+
+        Some(Self { id, content })
+    }
+
+    /// 块的纯文本，不支持的类型为空
+    pub fn plain_text(&self) -> &str {
+        match &self.content {
+            BlockContent::Paragraph { text, .. }
+            | BlockContent::BulletedListItem(text)
+            | BlockContent::NumberedListItem(text)
+            | BlockContent::Heading { text, .. }
+            | BlockContent::ToDo { text, .. } => text,
+            BlockContent::Unsupported(_) => "",
+        }
+    }
+}
+
+/// 拼接 rich_text 数组的 plain_text
+pub fn rich_text_to_plain(rich_text: &serde_json::Value) -> String {
+    rich_text
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|t| t.get("plain_text").and_then(|p| p.as_str()))
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+/// rich_text 为空，或只有一段没有链接、没有任何样式的普通文字
+fn is_plain_rich_text(rich_text: &serde_json::Value) -> bool {
+    let Some(parts) = rich_text.as_array() else {
+        return true;
+    };
+    match parts.as_slice() {
+        [] => true,
+        [part] => {
+            let annotated = part
+                .get("annotations")
+                .and_then(|a| a.as_object())
+                .is_some_and(|annotations| {
+                    annotations.iter().any(|(key, value)| match key.as_str() {
+                        "color" => value.as_str().is_some_and(|c| c != "default"),
+                        _ => value.as_bool() == Some(true),
+                    })
+                });
+            part.get("type").and_then(|t| t.as_str()) == Some("text")
+                && part.get("href").is_none_or(|h| h.is_null())
+                && !annotated
+        }
+        _ => false,
+    }
+}
+
+/// 块列表转成事件描述的纯文本
+pub fn blocks_to_description(blocks: &[NotionBlock]) -> String {
+    blocks
+        .iter()
+        .map(NotionBlock::plain_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -110,6 +557,75 @@ pub struct NotionListResponse<T> {
     pub next_cursor: Option<String>,
 }
 
+/// 转成 Notion 日期字符串：全天只有日期，否则带本地时区偏移的时间
+pub fn format_notion_date(date: NaiveDate, time: Option<NaiveTime>) -> String {
+    match time {
+        None => date.format("%Y-%m-%d").to_string(),
+        Some(time) => {
+            let naive = date.and_time(time);
+            match Local.from_local_datetime(&naive).earliest() {
+                Some(dt) => dt.to_rfc3339_opts(SecondsFormat::Secs, false),
+                None => naive.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            }
+        }
+    }
+}
+
+/// 解析 Notion 日期字符串，带时间的转成本地时间
+pub fn parse_notion_date(value: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    if value.len() == 10 {
+        return NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .ok()
+            .map(|date| (date, None));
+    }
+
+    let local = DateTime::parse_from_rfc3339(value)
+        .ok()?
+        .with_timezone(&Local)
+        .naive_local();
+    Some((local.date(), Some(local.time())))
+}
+
+/// 把事件的标题、日期和改动过的额外属性转成写入 Notion 的属性，属性名取自数据库配置
+///
+/// 只写回与 `before` 不同的标题、日期和可编辑属性：富文本只保留了纯文本和链接，原样写回会丢掉格式，
+/// 也会覆盖别人在 Notion 中的修改。新建时（`before` 为 None）总是写入标题和日期，以及所有填了值的可编辑属性。
+pub fn event_properties(
+    before: Option<&CalendarEvent>,
+    after: &CalendarEvent,
+    db_config: &DatabaseConfig,
+) -> PropertyMap {
+    let changed = |property: &EventProperty| match before {
+        Some(before) => !before
+            .properties
+            .iter()
+            .any(|p| p.name == property.name && p.value == property.value),
+        // 空值交给 Notion 的默认值
+        None => !property.value.plain_text().is_empty(),
+    };
+    let mut properties: PropertyMap = after
+        .properties
+        .iter()
+        .filter(|p| p.value.property_type().is_editable() && changed(p))
+        .map(|p| (p.name.clone(), p.value.clone()))
+        .collect();
+    if before.is_none_or(|b| b.title != after.title) {
+        properties.insert(
+            db_config.title_property.clone(),
+            PropertyValue::title(&after.title),
+        );
+    }
+    if before.is_none_or(|b| {
+        b.notion_start() != after.notion_start() || b.notion_end() != after.notion_end()
+    }) {
+        properties.insert(
+            db_config.date_property.clone(),
+            PropertyValue::date(after.notion_start(), after.notion_end()),
+        );
+    }
+    properties
+}
+
 /// 从 Notion 页面提取事件
 pub fn extract_event_from_page(
     page: &NotionPage,
@@ -119,38 +635,39 @@ pub fn extract_event_from_page(
 
     let title = props
         .get(&db_config.title_property)
-        .and_then(|p| p.get("title"))
-        .and_then(|t| t.as_array())
-        .and_then(|arr| arr.first())
-        .and_then(|t| t.get("plain_text"))
-        .and_then(|t| t.as_str())
-        .map(|s| s.to_string())
+        .map(PropertyValue::plain_text)
+        .filter(|t| !t.is_empty())
         .unwrap_or_else(|| "无标题".to_string());
 
-    let date_value = props.get(&db_config.date_property)?;
-    let date_range = date_value.get("date")?;
+    let date_range = props.get(&db_config.date_property)?.as_date()?;
 
-    let start_str = date_range.get("start")?.as_str()?;
-    let start_date = if start_str.len() == 10 {
-        NaiveDate::parse_from_str(start_str, "%Y-%m-%d").ok()?
-    } else {
-        DateTime::parse_from_rfc3339(start_str)
-            .ok()
-            .map(|dt| dt.naive_local().date())?
-    };
+    let (start_date, start_time) = parse_notion_date(&date_range.start)?;
 
-    let end_date = date_range
-        .get("end")
-        .and_then(|e| e.as_str())
-        .and_then(|s| {
-            if s.len() == 10 {
-                NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()
-            } else {
-                DateTime::parse_from_rfc3339(s)
-                    .ok()
-                    .map(|dt| dt.naive_local().date())
-            }
-        });
+    let (end_date, end_time) = date_range
+        .end
+        .as_deref()
+        .and_then(parse_notion_date)
+        .map_or((None, None), |(date, time)| (Some(date), time));
+
+    let (color_label, color) = db_config.event_color(props);
+
+    let properties = db_config
+        .extra_properties
+        .iter()
+        .filter_map(|mapping| {
+            Some(EventProperty {
+                name: mapping.property.clone(),
+                slot: mapping.slot,
+                value: props.get(&mapping.property)?.clone(),
+            })
+        })
+        .collect();
+
+    let parse_time = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now())
+    };
 
     Some(CalendarEvent {
         id: Uuid::new_v4().to_string(),
@@ -159,10 +676,146 @@ pub fn extract_event_from_page(
         notion_page_id: Some(page.id.clone()),
         start_date,
         end_date,
-        all_day: true,
+        start_time,
+        end_time,
+        all_day: start_time.is_none(),
         description: None,
-        color: db_config.color.clone(),
-        created_at: Utc::now(),
-        updated_at: Utc::now(),
+        color,
+        color_label,
+        properties,
+        created_at: parse_time(&page.created_time),
+        updated_at: parse_time(&page.last_edited_time),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paragraph(rich_text: serde_json::Value) -> BlockContent {
+        NotionBlock::from_value(&json!({
+            "id": "b1",
+            "type": "paragraph",
+            "paragraph": { "rich_text": rich_text }
+        }))
+        .unwrap()
+        .content
+    }
+
+    fn run(text: &str, annotations: serde_json::Value) -> serde_json::Value {
+        json!({
+            "type": "text",
+            "text": { "content": text, "link": null },
+            "plain_text": text,
+            "href": null,
+            "annotations": annotations
+        })
+    }
+
+    fn with_number(event: CalendarEvent, number: f64) -> CalendarEvent {
+        CalendarEvent {
+            properties: vec![EventProperty {
+                name: "Points".to_string(),
+                slot: DisplaySlot::Badge,
+                value: PropertyValue::Number {
+                    number: Some(number),
+                },
+            }],
+            ..event
+        }
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn event_properties_on_create_writes_title_date_and_values() {
+        let config = DatabaseConfig::default();
+        let event = with_number(CalendarEvent::fixture("a", day(1)), 3.0);
+
+        let properties = event_properties(None, &event, &config);
+
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["Date", "Name", "Points"]
+        );
+    }
+
+    #[test]
+    fn event_properties_unchanged_writes_nothing() {
+        let config = DatabaseConfig::default();
+        let event = with_number(CalendarEvent::fixture("a", day(1)), 3.0);
+
+        assert!(event_properties(Some(&event), &event, &config).is_empty());
+    }
+
+    #[test]
+    fn event_properties_writes_only_changed_fields() {
+        let config = DatabaseConfig::default();
+        let before = with_number(CalendarEvent::fixture("a", day(1)), 3.0);
+
+        let renamed = CalendarEvent {
+            title: "b".to_string(),
+            ..before.clone()
+        };
+        let properties = event_properties(Some(&before), &renamed, &config);
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["Name"]);
+
+        let moved = CalendarEvent {
+            start_date: day(2),
+            ..before.clone()
+        };
+        let properties = event_properties(Some(&before), &moved, &config);
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["Date"]);
+
+        let rescored = with_number(before.clone(), 5.0);
+        let properties = event_properties(Some(&before), &rescored, &config);
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["Points"]);
+    }
+
+    #[test]
+    fn paragraph_plain_only_for_single_unformatted_run() {
+        let default = json!({
+            "bold": false, "italic": false, "strikethrough": false,
+            "underline": false, "code": false, "color": "default"
+        });
+        let bold = json!({ "bold": true, "color": "default" });
+        let red = json!({ "bold": false, "color": "red" });
+
+        let cases = [
+            (json!([]), true),
+            (json!([run("a", default.clone())]), true),
+            (json!([run("a", bold)]), false),
+            (json!([run("a", red)]), false),
+            (
+                json!([run("a", default.clone()), run("b", default.clone())]),
+                false,
+            ),
+            (
+                json!([{ "type": "mention", "plain_text": "@x", "href": null, "annotations": default }]),
+                false,
+            ),
+        ];
+        for (rich_text, expected) in cases {
+            let BlockContent::Paragraph { plain, .. } = paragraph(rich_text.clone()) else {
+                panic!("应解析为段落");
+            };
+            assert_eq!(plain, expected, "{}", rich_text);
+        }
+    }
+
+    #[test]
+    fn paragraph_with_link_is_not_plain() {
+        let mut linked = run("a", json!({ "color": "default" }));
+        linked["href"] = json!("https://example.com");
+        assert_eq!(
+            paragraph(json!([linked])),
+            BlockContent::Paragraph {
+                text: "a".to_string(),
+                plain: false
+            }
+        );
+    }
+}
@@ -0,0 +1,860 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::json;
+
+/// 页面属性：属性名 -> 值
+pub type PropertyMap = BTreeMap<String, PropertyValue>;
+
+/// Notion 单个 text 对象最多 2000 个字符
+const MAX_TEXT_LENGTH: usize = 2000;
+
+/// 富文本片段，只保留纯文本和链接
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RichText {
+    pub plain_text: String,
+    #[serde(default)]
+    pub href: Option<String>,
+}
+
+/// select / multi_select / status 的选项
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SelectOption {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 日期值，`start` / `end` 为 Notion 的日期或带偏移的时间字符串
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DateValue {
+    pub start: String,
+    #[serde(default)]
+    pub end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NotionUser {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelationRef {
+    pub id: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FormulaValue {
+    String { string: Option<String> },
+    Number { number: Option<f64> },
+    Boolean { boolean: Option<bool> },
+    Date { date: Option<DateValue> },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RollupValue {
+    Number {
+        number: Option<f64>,
+    },
+    Date {
+        date: Option<DateValue>,
+    },
+    Array {
+        array: Vec<PropertyValue>,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+/// 页面属性值
+///
+/// 与 Notion 返回的 JSON 结构一一对应（以 `type` 区分），写回时用
+/// [`PropertyValue::to_request`] 生成请求体。
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PropertyValue {
+    Title {
+        title: Vec<RichText>,
+    },
+    RichText {
+        rich_text: Vec<RichText>,
+    },
+    Number {
+        number: Option<f64>,
+    },
+    Select {
+        select: Option<SelectOption>,
+    },
+    MultiSelect {
+        multi_select: Vec<SelectOption>,
+    },
+    Status {
+        status: Option<SelectOption>,
+    },
+    Date {
+        date: Option<DateValue>,
+    },
+    People {
+        people: Vec<NotionUser>,
+    },
+    Checkbox {
+        checkbox: bool,
+    },
+    Url {
+        url: Option<String>,
+    },
+    Email {
+        email: Option<String>,
+    },
+    PhoneNumber {
+        phone_number: Option<String>,
+    },
+    Relation {
+        relation: Vec<RelationRef>,
+    },
+    Formula {
+        formula: FormulaValue,
+    },
+    Rollup {
+        rollup: RollupValue,
+    },
+    /// 其他类型（文件、创建时间等），只读且不显示
+    #[serde(other)]
+    Unsupported,
+}
+
+/// 属性类型，数据库结构和属性值共用
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyType {
+    Title,
+    RichText,
+    Number,
+    Select,
+    MultiSelect,
+    Status,
+    Date,
+    People,
+    Checkbox,
+    Url,
+    Email,
+    PhoneNumber,
+    Relation,
+    Formula,
+    Rollup,
+    #[serde(other)]
+    Unsupported,
+}
+
+impl PropertyType {
+    /// 是否可以通过 API 写入
+    pub fn is_writable(&self) -> bool {
+        !matches!(
+            self,
+            PropertyType::Formula | PropertyType::Rollup | PropertyType::Unsupported
+        )
+    }
+
+    /// 是否可以在事件编辑框中修改
+    ///
+    /// 关联需要选择其他数据库的页面，暂时只读。
+    pub fn is_editable(&self) -> bool {
+        self.is_writable() && !matches!(self, PropertyType::Relation)
+    }
+}
+
+impl PropertyValue {
+    pub fn title(text: &str) -> Self {
+        PropertyValue::Title {
+            title: vec![RichText {
+                plain_text: text.to_string(),
+                href: None,
+            }],
+        }
+    }
+
+    pub fn date(start: String, end: Option<String>) -> Self {
+        PropertyValue::Date {
+            date: Some(DateValue {
+                start,
+                end,
+                time_zone: None,
+            }),
+        }
+    }
+
+    /// 新建页面时可编辑属性的空值，不可编辑的类型返回 None
+    pub fn empty(kind: PropertyType) -> Option<Self> {
+        if !kind.is_editable() {
+            return None;
+        }
+        Some(match kind {
+            PropertyType::Title => PropertyValue::Title { title: Vec::new() },
+            PropertyType::RichText => PropertyValue::RichText {
+                rich_text: Vec::new(),
+            },
+            PropertyType::Number => PropertyValue::Number { number: None },
+            PropertyType::Select => PropertyValue::Select { select: None },
+            PropertyType::MultiSelect => PropertyValue::MultiSelect {
+                multi_select: Vec::new(),
+            },
+            PropertyType::Status => PropertyValue::Status { status: None },
+            PropertyType::Date => PropertyValue::Date { date: None },
+            PropertyType::People => PropertyValue::People { people: Vec::new() },
+            PropertyType::Checkbox => PropertyValue::Checkbox { checkbox: false },
+            PropertyType::Url => PropertyValue::Url { url: None },
+            PropertyType::Email => PropertyValue::Email { email: None },
+            PropertyType::PhoneNumber => PropertyValue::PhoneNumber { phone_number: None },
+            PropertyType::Relation
+            | PropertyType::Formula
+            | PropertyType::Rollup
+            | PropertyType::Unsupported => return None,
+        })
+    }
+
+    pub fn property_type(&self) -> PropertyType {
+        match self {
+            PropertyValue::Title { .. } => PropertyType::Title,
+            PropertyValue::RichText { .. } => PropertyType::RichText,
+            PropertyValue::Number { .. } => PropertyType::Number,
+            PropertyValue::Select { .. } => PropertyType::Select,
+            PropertyValue::MultiSelect { .. } => PropertyType::MultiSelect,
+            PropertyValue::Status { .. } => PropertyType::Status,
+            PropertyValue::Date { .. } => PropertyType::Date,
+            PropertyValue::People { .. } => PropertyType::People,
+            PropertyValue::Checkbox { .. } => PropertyType::Checkbox,
+            PropertyValue::Url { .. } => PropertyType::Url,
+            PropertyValue::Email { .. } => PropertyType::Email,
+            PropertyValue::PhoneNumber { .. } => PropertyType::PhoneNumber,
+            PropertyValue::Relation { .. } => PropertyType::Relation,
+            PropertyValue::Formula { .. } => PropertyType::Formula,
+            PropertyValue::Rollup { .. } => PropertyType::Rollup,
+            PropertyValue::Unsupported => PropertyType::Unsupported,
+        }
+    }
+
+    /// select / status 当前选中的选项
+    pub fn selected_option(&self) -> Option<&SelectOption> {
+        match self {
+            PropertyValue::Select { select: option } | PropertyValue::Status { status: option } => {
+                option.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// 按属性分组时所属的组：select / status 的选项，或 people 中的每个人
+    pub fn group_names(&self) -> Vec<String> {
+        match self {
+            PropertyValue::People { people } => people
+                .iter()
+                .map(|p| p.name.clone().unwrap_or_else(|| p.id.clone()))
+                .collect(),
+            _ => self
+                .selected_option()
+                .map(|option| vec![option.name.clone()])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 日期值，公式和汇总计算出的日期也算
+    pub fn as_date(&self) -> Option<&DateValue> {
+        match self {
+            PropertyValue::Date { date } => date.as_ref(),
+            PropertyValue::Formula {
+                formula: FormulaValue::Date { date },
+            } => date.as_ref(),
+            PropertyValue::Rollup {
+                rollup: RollupValue::Date { date },
+            } => date.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// 用于显示的纯文本
+    pub fn plain_text(&self) -> String {
+        fn number_text(number: &Option<f64>) -> String {
+            number.map(|n| n.to_string()).unwrap_or_default()
+        }
+        fn date_text(date: &Option<DateValue>) -> String {
+            match date {
+                Some(DateValue {
+                    start,
+                    end: Some(end),
+                    ..
+                }) => format!("{} → {}", start, end),
+                Some(DateValue { start, .. }) => start.clone(),
+                None => String::new(),
+            }
+        }
+
+        match self {
+            PropertyValue::Title { title: text } | PropertyValue::RichText { rich_text: text } => {
+                text.iter().map(|t| t.plain_text.as_str()).collect()
+            }
+            PropertyValue::Number { number } => number_text(number),
+            PropertyValue::Select { select: option } | PropertyValue::Status { status: option } => {
+                option.as_ref().map(|o| o.name.clone()).unwrap_or_default()
+            }
+            PropertyValue::MultiSelect { multi_select } => multi_select
+                .iter()
+                .map(|o| o.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            PropertyValue::Date { date } => date_text(date),
+            PropertyValue::People { people } => people
+                .iter()
+                .map(|p| p.name.as_deref().unwrap_or(p.id.as_str()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            PropertyValue::Checkbox { checkbox } => if *checkbox { "✓" } else { "" }.to_string(),
+            PropertyValue::Url { url: text }
+            | PropertyValue::Email { email: text }
+            | PropertyValue::PhoneNumber { phone_number: text } => text.clone().unwrap_or_default(),
+            PropertyValue::Relation { relation } => format!("{} 个关联", relation.len()),
+            PropertyValue::Formula { formula } => match formula {
+                FormulaValue::String { string } => string.clone().unwrap_or_default(),
+                FormulaValue::Number { number } => number_text(number),
+                FormulaValue::Boolean { boolean } => {
+                    if boolean.unwrap_or(false) { "✓" } else { "" }.to_string()
+                }
+                FormulaValue::Date { date } => date_text(date),
+            },
+            PropertyValue::Rollup { rollup } => match rollup {
+                RollupValue::Number { number } => number_text(number),
+                RollupValue::Date { date } => date_text(date),
+                RollupValue::Array { array } => array
+                    .iter()
+                    .map(PropertyValue::plain_text)
+                    .filter(|t| !t.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                RollupValue::Unsupported => String::new(),
+            },
+            PropertyValue::Unsupported => String::new(),
+        }
+    }
+
+    /// 写入页面时的请求体；公式、汇总等只读类型返回 None
+    pub fn to_request(&self) -> Option<serde_json::Value> {
+        let names = |options: &[SelectOption]| -> Vec<serde_json::Value> {
+            options.iter().map(|o| json!({ "name": o.name })).collect()
+        };
+        let ids = |ids: Vec<&String>| -> Vec<serde_json::Value> {
+            ids.into_iter().map(|id| json!({ "id": id })).collect()
+        };
+
+        Some(match self {
+            PropertyValue::Title { title } => json!({ "title": rich_text_request(title) }),
+            PropertyValue::RichText { rich_text } => {
+                json!({ "rich_text": rich_text_request(rich_text) })
+            }
+            PropertyValue::Number { number } => json!({ "number": number }),
+            PropertyValue::Select { select } => {
+                json!({ "select": select.as_ref().map(|o| json!({ "name": o.name })) })
+            }
+            PropertyValue::MultiSelect { multi_select } => {
+                json!({ "multi_select": names(multi_select) })
+            }
+            PropertyValue::Status { status } => {
+                json!({ "status": status.as_ref().map(|o| json!({ "name": o.name })) })
+            }
+            PropertyValue::Date { date } => json!({ "date": date }),
+            PropertyValue::People { people } => {
+                json!({ "people": ids(people.iter().map(|p| &p.id).collect()) })
+            }
+            PropertyValue::Checkbox { checkbox } => json!({ "checkbox": checkbox }),
+            PropertyValue::Url { url } => json!({ "url": url }),
+            PropertyValue::Email { email } => json!({ "email": email }),
+            PropertyValue::PhoneNumber { phone_number } => json!({ "phone_number": phone_number }),
+            PropertyValue::Relation { relation } => {
+                json!({ "relation": ids(relation.iter().map(|r| &r.id).collect()) })
+            }
+            PropertyValue::Formula { .. }
+            | PropertyValue::Rollup { .. }
+            | PropertyValue::Unsupported => return None,
+        })
+    }
+}
+
+/// 纯文本转 rich_text 请求数组，超长时拆成多段
+pub fn plain_rich_text(content: &str) -> serde_json::Value {
+    rich_text_request(&[RichText {
+        plain_text: content.to_string(),
+        href: None,
+    }])
+}
+
+fn rich_text_request(parts: &[RichText]) -> serde_json::Value {
+    let mut request = Vec::new();
+    for part in parts {
+        let chars: Vec<char> = part.plain_text.chars().collect();
+        for chunk in chars.chunks(MAX_TEXT_LENGTH) {
+            let content: String = chunk.iter().collect();
+            request.push(match part.href {
+                Some(ref href) => {
+                    json!({ "text": { "content": content, "link": { "url": href } } })
+                }
+                None => json!({ "text": { "content": content } }),
+            });
+        }
+    }
+    json!(request)
+}
+
+/// 整页属性的请求体，跳过只读属性
+pub fn properties_request(properties: &PropertyMap) -> serde_json::Value {
+    let body: serde_json::Map<String, serde_json::Value> = properties
+        .iter()
+        .filter_map(|(name, value)| Some((name.clone(), value.to_request()?)))
+        .collect();
+    serde_json::Value::Object(body)
+}
+
+/// 逐个解析属性，无法识别的值当作 Unsupported，避免一个属性让整页解析失败
+pub fn deserialize_properties<'de, D>(deserializer: D) -> Result<PropertyMap, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = BTreeMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(raw
+        .into_iter()
+        .map(|(name, value)| {
+            let value = serde_json::from_value(value).unwrap_or(PropertyValue::Unsupported);
+            (name, value)
+        })
+        .collect())
+}
+
+/// 数据库中一个属性的定义
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(from = "RawPropertySchema")]
+pub struct PropertySchema {
+    pub id: String,
+    pub name: String,
+    pub kind: PropertyType,
+    /// select / multi_select / status 的可选项
+    pub options: Vec<SelectOption>,
+}
+
+#[derive(Deserialize)]
+struct RawPropertySchema {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    kind: PropertyType,
+    #[serde(flatten)]
+    config: BTreeMap<String, serde_json::Value>,
+}
+
+impl From<RawPropertySchema> for PropertySchema {
+    fn from(raw: RawPropertySchema) -> Self {
+        // 选项位于与类型同名的字段下，如 {"type": "select", "select": {"options": [...]}}
+        let options = raw
+            .config
+            .values()
+            .filter_map(|v| v.get("options"))
+            .find_map(|options| serde_json::from_value(options.clone()).ok())
+            .unwrap_or_default();
+
+        Self {
+            id: raw.id,
+            name: raw.name,
+            kind: raw.kind,
+            options,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: serde_json::Value) -> PropertyValue {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn option(name: &str) -> SelectOption {
+        SelectOption {
+            id: Some(format!("{}-id", name)),
+            name: name.to_string(),
+            color: Some("blue".to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_title_and_rich_text() {
+        let rich_text = json!([{
+            "type": "text",
+            "text": { "content": "Notion", "link": { "url": "https://notion.so" } },
+            "annotations": { "bold": true, "italic": false, "color": "default" },
+            "plain_text": "Notion",
+            "href": "https://notion.so"
+        }]);
+        let expected = vec![RichText {
+            plain_text: "Notion".to_string(),
+            href: Some("https://notion.so".to_string()),
+        }];
+
+        assert_eq!(
+            parse(json!({ "id": "title", "type": "title", "title": rich_text })),
+            PropertyValue::Title {
+                title: expected.clone()
+            }
+        );
+        assert_eq!(
+            parse(json!({ "id": "%3Bx", "type": "rich_text", "rich_text": rich_text })),
+            PropertyValue::RichText {
+                rich_text: expected
+            }
+        );
+    }
+
+    #[test]
+    fn parses_number_checkbox_and_text_fields() {
+        assert_eq!(
+            parse(json!({ "id": "a", "type": "number", "number": 2.5 })),
+            PropertyValue::Number { number: Some(2.5) }
+        );
+        assert_eq!(
+            parse(json!({ "id": "a", "type": "number", "number": null })),
+            PropertyValue::Number { number: None }
+        );
+        assert_eq!(
+            parse(json!({ "id": "b", "type": "checkbox", "checkbox": true })),
+            PropertyValue::Checkbox { checkbox: true }
+        );
+        assert_eq!(
+            parse(json!({ "id": "c", "type": "url", "url": "https://example.com" })),
+            PropertyValue::Url {
+                url: Some("https://example.com".to_string())
+            }
+        );
+        assert_eq!(
+            parse(json!({ "id": "d", "type": "email", "email": "a@example.com" })),
+            PropertyValue::Email {
+                email: Some("a@example.com".to_string())
+            }
+        );
+        assert_eq!(
+            parse(json!({ "id": "e", "type": "phone_number", "phone_number": null })),
+            PropertyValue::PhoneNumber { phone_number: None }
+        );
+    }
+
+    #[test]
+    fn parses_options() {
+        let raw =
+            |name: &str| json!({ "id": format!("{}-id", name), "name": name, "color": "blue" });
+
+        assert_eq!(
+            parse(json!({ "id": "a", "type": "select", "select": raw("会议") })),
+            PropertyValue::Select {
+                select: Some(option("会议"))
+            }
+        );
+        assert_eq!(
+            parse(json!({ "id": "a", "type": "select", "select": null })),
+            PropertyValue::Select { select: None }
+        );
+        assert_eq!(
+            parse(
+                json!({ "id": "b", "type": "multi_select", "multi_select": [raw("工作"), raw("重要")] })
+            ),
+            PropertyValue::MultiSelect {
+                multi_select: vec![option("工作"), option("重要")]
+            }
+        );
+        assert_eq!(
+            parse(json!({ "id": "c", "type": "status", "status": raw("进行中") })),
+            PropertyValue::Status {
+                status: Some(option("进行中"))
+            }
+        );
+    }
+
+    #[test]
+    fn parses_date_people_and_relation() {
+        assert_eq!(
+            parse(json!({
+                "id": "a",
+                "type": "date",
+                "date": { "start": "2024-03-01T09:00:00.000+08:00", "end": null, "time_zone": null }
+            })),
+            PropertyValue::date("2024-03-01T09:00:00.000+08:00".to_string(), None)
+        );
+        assert_eq!(
+            parse(json!({
+                "id": "b",
+                "type": "people",
+                "people": [
+                    { "object": "user", "id": "u1", "name": "小明", "avatar_url": null, "type": "person", "person": {} },
+                    { "object": "user", "id": "u2" }
+                ]
+            })),
+            PropertyValue::People {
+                people: vec![
+                    NotionUser {
+                        id: "u1".to_string(),
+                        name: Some("小明".to_string())
+                    },
+                    NotionUser {
+                        id: "u2".to_string(),
+                        name: None
+                    },
+                ]
+            }
+        );
+        assert_eq!(
+            parse(
+                json!({ "id": "c", "type": "relation", "relation": [{ "id": "p1" }], "has_more": false })
+            ),
+            PropertyValue::Relation {
+                relation: vec![RelationRef {
+                    id: "p1".to_string()
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn parses_formula_and_rollup() {
+        assert_eq!(
+            parse(
+                json!({ "id": "a", "type": "formula", "formula": { "type": "string", "string": "ok" } })
+            ),
+            PropertyValue::Formula {
+                formula: FormulaValue::String {
+                    string: Some("ok".to_string())
+                }
+            }
+        );
+        let formula_date = parse(json!({
+            "id": "a",
+            "type": "formula",
+            "formula": { "type": "date", "date": { "start": "2024-03-01", "end": null } }
+        }));
+        assert_eq!(
+            formula_date.as_date().map(|d| d.start.as_str()),
+            Some("2024-03-01")
+        );
+
+        assert_eq!(
+            parse(json!({
+                "id": "b",
+                "type": "rollup",
+                "rollup": { "type": "number", "number": 3, "function": "count" }
+            })),
+            PropertyValue::Rollup {
+                rollup: RollupValue::Number { number: Some(3.0) }
+            }
+        );
+        let array = parse(json!({
+            "id": "b",
+            "type": "rollup",
+            "rollup": {
+                "type": "array",
+                "array": [{ "type": "title", "title": [{ "plain_text": "甲", "href": null }] }],
+                "function": "show_original"
+            }
+        }));
+        assert_eq!(array.plain_text(), "甲");
+        assert_eq!(
+            parse(json!({
+                "id": "b",
+                "type": "rollup",
+                "rollup": { "type": "incomplete", "incomplete": {}, "function": "show_original" }
+            })),
+            PropertyValue::Rollup {
+                rollup: RollupValue::Unsupported
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_types_are_unsupported() {
+        assert_eq!(
+            parse(json!({ "id": "a", "type": "files", "files": [] })),
+            PropertyValue::Unsupported
+        );
+        assert_eq!(
+            parse(
+                json!({ "id": "b", "type": "created_time", "created_time": "2024-03-01T00:00:00.000Z" })
+            ),
+            PropertyValue::Unsupported
+        );
+
+        // 已知类型但结构不对时也不影响其他属性
+        #[derive(Deserialize)]
+        struct Page {
+            #[serde(deserialize_with = "deserialize_properties")]
+            properties: PropertyMap,
+        }
+        let page: Page = serde_json::from_value(json!({
+            "properties": {
+                "名称": { "id": "title", "type": "title", "title": [] },
+                "坏掉的": { "id": "x", "type": "number", "number": "abc" }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            page.properties["名称"],
+            PropertyValue::Title { title: vec![] }
+        );
+        assert_eq!(page.properties["坏掉的"], PropertyValue::Unsupported);
+    }
+
+    #[test]
+    fn builds_request_for_writable_types() {
+        let properties: PropertyMap = [
+            (
+                "title",
+                PropertyValue::Title {
+                    title: vec![RichText {
+                        plain_text: "周会".to_string(),
+                        href: Some("https://example.com".to_string()),
+                    }],
+                },
+            ),
+            (
+                "rich_text",
+                PropertyValue::RichText {
+                    rich_text: vec![RichText {
+                        plain_text: "备注".to_string(),
+                        href: None,
+                    }],
+                },
+            ),
+            ("number", PropertyValue::Number { number: Some(1.5) }),
+            (
+                "select",
+                PropertyValue::Select {
+                    select: Some(option("会议")),
+                },
+            ),
+            ("select_empty", PropertyValue::Select { select: None }),
+            (
+                "multi_select",
+                PropertyValue::MultiSelect {
+                    multi_select: vec![option("工作")],
+                },
+            ),
+            (
+                "status",
+                PropertyValue::Status {
+                    status: Some(option("完成")),
+                },
+            ),
+            (
+                "date",
+                PropertyValue::date("2024-03-01".to_string(), Some("2024-03-02".to_string())),
+            ),
+            (
+                "people",
+                PropertyValue::People {
+                    people: vec![NotionUser {
+                        id: "u1".to_string(),
+                        name: Some("小明".to_string()),
+                    }],
+                },
+            ),
+            ("checkbox", PropertyValue::Checkbox { checkbox: false }),
+            ("url", PropertyValue::Url { url: None }),
+            (
+                "email",
+                PropertyValue::Email {
+                    email: Some("a@example.com".to_string()),
+                },
+            ),
+            (
+                "phone_number",
+                PropertyValue::PhoneNumber {
+                    phone_number: Some("123".to_string()),
+                },
+            ),
+            (
+                "relation",
+                PropertyValue::Relation {
+                    relation: vec![RelationRef {
+                        id: "p1".to_string(),
+                    }],
+                },
+            ),
+            (
+                "formula",
+                PropertyValue::Formula {
+                    formula: FormulaValue::Number { number: Some(1.0) },
+                },
+            ),
+            (
+                "rollup",
+                PropertyValue::Rollup {
+                    rollup: RollupValue::Unsupported,
+                },
+            ),
+            ("unsupported", PropertyValue::Unsupported),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+
+        assert_eq!(
+            properties_request(&properties),
+            json!({
+                "title": { "title": [{ "text": { "content": "周会", "link": { "url": "https://example.com" } } }] },
+                "rich_text": { "rich_text": [{ "text": { "content": "备注" } }] },
+                "number": { "number": 1.5 },
+                "select": { "select": { "name": "会议" } },
+                "select_empty": { "select": null },
+                "multi_select": { "multi_select": [{ "name": "工作" }] },
+                "status": { "status": { "name": "完成" } },
+                "date": { "date": { "start": "2024-03-01", "end": "2024-03-02" } },
+                "people": { "people": [{ "id": "u1" }] },
+                "checkbox": { "checkbox": false },
+                "url": { "url": null },
+                "email": { "email": "a@example.com" },
+                "phone_number": { "phone_number": "123" },
+                "relation": { "relation": [{ "id": "p1" }] }
+            })
+        );
+    }
+
+    #[test]
+    fn empty_values_match_their_type() {
+        for kind in [
+            PropertyType::RichText,
+            PropertyType::Number,
+            PropertyType::Select,
+            PropertyType::MultiSelect,
+            PropertyType::Status,
+            PropertyType::Date,
+            PropertyType::People,
+            PropertyType::Checkbox,
+            PropertyType::Url,
+            PropertyType::Email,
+            PropertyType::PhoneNumber,
+        ] {
+            let value = PropertyValue::empty(kind).unwrap();
+            assert_eq!(value.property_type(), kind);
+            assert_eq!(value.plain_text(), "");
+        }
+        assert_eq!(PropertyValue::empty(PropertyType::Relation), None);
+        assert_eq!(PropertyValue::empty(PropertyType::Formula), None);
+    }
+
+    #[test]
+    fn splits_long_text() {
+        let request = plain_rich_text(&"字".repeat(MAX_TEXT_LENGTH + 1));
+        let parts = request.as_array().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1]["text"]["content"], "字");
+    }
+}
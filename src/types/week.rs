@@ -0,0 +1,69 @@
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+/// 一周从哪一天开始
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeekStart {
+    Sunday,
+    #[default]
+    Monday,
+    Saturday,
+}
+
+impl WeekStart {
+    pub const ALL: [WeekStart; 3] = [WeekStart::Monday, WeekStart::Sunday, WeekStart::Saturday];
+
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            WeekStart::Sunday => Weekday::Sun,
+            WeekStart::Monday => Weekday::Mon,
+            WeekStart::Saturday => Weekday::Sat,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeekStart::Sunday => "周日",
+            WeekStart::Monday => "周一",
+            WeekStart::Saturday => "周六",
+        }
+    }
+
+    /// 一周七天的顺序
+    pub fn weekdays(&self) -> [Weekday; 7] {
+        let mut day = self.weekday();
+        [(); 7].map(|_| {
+            let current = day;
+            day = day.succ();
+            current
+        })
+    }
+}
+
+/// 周数的计算方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeekNumbering {
+    /// ISO 8601：周一开始，包含当年第一个周四的那一周为第 1 周
+    Iso,
+    /// 美国习惯：周日开始，包含 1 月 1 日的那一周为第 1 周
+    Us,
+}
+
+impl WeekNumbering {
+    pub const ALL: [WeekNumbering; 2] = [WeekNumbering::Iso, WeekNumbering::Us];
+
+    /// 这种计算方式中一周的第一天
+    pub fn first_day(&self) -> Weekday {
+        match self {
+            WeekNumbering::Iso => Weekday::Mon,
+            WeekNumbering::Us => Weekday::Sun,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeekNumbering::Iso => "ISO 8601",
+            WeekNumbering::Us => "美国",
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use super::navigation::{days_in_month, week_start};
+
+/// 月历网格：从当月 1 号所在周的第一天开始，按整周排列，前后用相邻月份的日期补齐
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MonthGrid {
+    pub year: i32,
+    pub month: u32,
+    /// 网格第一格的日期
+    pub start: NaiveDate,
+    /// 行数（周数）
+    pub weeks: usize,
+}
+
+impl MonthGrid {
+    /// 某月的网格，每行从 `first_weekday` 开始
+    pub fn new(year: i32, month: u32, first_weekday: Weekday) -> Self {
+        let first_day = NaiveDate::from_ymd_opt(year, month, 1).expect("月份应在 1-12 之间");
+        let start = week_start(first_day, first_weekday);
+        let leading = (first_day - start).num_days() as usize;
+        let weeks = (leading + days_in_month(year, month) as usize).div_ceil(7);
+        Self {
+            year,
+            month,
+            start,
+            weeks,
+        }
+    }
+
+    /// 日期所在月份的网格
+    pub fn for_date(date: NaiveDate, first_weekday: Weekday) -> Self {
+        Self::new(date.year(), date.month(), first_weekday)
+    }
+
+    /// 网格最后一格的日期
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(self.weeks as i64 * 7 - 1)
+    }
+
+    /// 每一行第一格的日期
+    pub fn week_starts(&self) -> impl Iterator<Item = NaiveDate> {
+        let start = self.start;
+        (0..self.weeks as i64).map(move |row| start + Duration::weeks(row))
+    }
+
+    /// 日期是否属于本月（不是补齐的格子）
+    pub fn in_month(&self, date: NaiveDate) -> bool {
+        date.year() == self.year && date.month() == self.month
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn month_starting_on_monday_has_no_leading_days() {
+        // 2021 年 2 月 1 日是周一，28 天正好四周
+        let grid = MonthGrid::new(2021, 2, Weekday::Mon);
+        assert_eq!(grid.start, date(2021, 2, 1));
+        assert_eq!(grid.weeks, 4);
+        assert_eq!(grid.end(), date(2021, 2, 28));
+    }
+
+    #[test]
+    fn pads_with_neighbouring_months() {
+        // 2026 年 3 月 1 日是周日
+        let grid = MonthGrid::new(2026, 3, Weekday::Mon);
+        assert_eq!(grid.start, date(2026, 2, 23));
+        assert_eq!(grid.weeks, 6);
+        assert_eq!(grid.end(), date(2026, 4, 5));
+        assert!(!grid.in_month(date(2026, 2, 28)));
+        assert!(grid.in_month(date(2026, 3, 31)));
+        assert_eq!(
+            grid.week_starts().collect::<Vec<_>>(),
+            vec![
+                date(2026, 2, 23),
+                date(2026, 3, 2),
+                date(2026, 3, 9),
+                date(2026, 3, 16),
+                date(2026, 3, 23),
+                date(2026, 3, 30),
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_follow_week_start() {
+        // 2026 年 3 月 1 日是周日：周日开始时没有补齐的格子
+        let grid = MonthGrid::new(2026, 3, Weekday::Sun);
+        assert_eq!(grid.start, date(2026, 3, 1));
+        assert_eq!(grid.weeks, 5);
+        assert_eq!(grid.end(), date(2026, 4, 4));
+
+        let grid = MonthGrid::new(2026, 3, Weekday::Sat);
+        assert_eq!(grid.start, date(2026, 2, 28));
+        assert_eq!(grid.weeks, 5);
+        assert_eq!(grid.end(), date(2026, 4, 3));
+    }
+
+    #[test]
+    fn grid_for_date_uses_its_month() {
+        assert_eq!(
+            MonthGrid::for_date(date(2026, 12, 31), Weekday::Mon),
+            MonthGrid::new(2026, 12, Weekday::Mon)
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use serde_json::Value;
+
+use super::parse_notion_url;
+
+/// 当前 `AppState` 存储格式的版本
+///
+/// 修改 `AppState` 或其中的类型、且旧数据无法靠 `#[serde(default)]` 读取时，
+/// 版本号加一，并在 [`MIGRATIONS`] 末尾追加对应的迁移。
+pub const STATE_VERSION: u64 = 1;
+
+/// 存储中记录版本号的字段
+pub const VERSION_FIELD: &str = "version";
+
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// 第 i 项把版本 i 的数据迁移到版本 i + 1
+const MIGRATIONS: [Migration; STATE_VERSION as usize] = [migrate_v0_clean_database_ids];
+
+/// 把任意旧版本的状态迁移到当前版本，没有版本号的视为版本 0
+pub fn migrate_state(mut value: Value) -> Result<Value, String> {
+    let version = value
+        .get(VERSION_FIELD)
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if version > STATE_VERSION {
+        return Err(format!("存储的状态版本 {} 比当前程序新", version));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(&mut value).map_err(|e| format!("从版本 {} 迁移失败: {}", from, e))?;
+    }
+
+    set_version(&mut value);
+    Ok(value)
+}
+
+/// 写入当前版本号
+pub fn set_version(value: &mut Value) {
+    if let Some(object) = value.as_object_mut() {
+        object.insert(VERSION_FIELD.to_string(), Value::from(STATE_VERSION));
+    }
+}
+
+/// v0 -> v1：旧版本只去掉连字符，粘贴的链接会原样保存成无效 ID，这里重新解析
+fn migrate_v0_clean_database_ids(value: &mut Value) -> Result<(), String> {
+    let Some(databases) = value.get_mut("databases").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    for database in databases {
+        let Some(id) = database.get_mut("notion_database_id") else {
+            continue;
+        };
+        if let Some(ids) = id.as_str().and_then(parse_notion_url) {
+            *id = Value::from(ids.database_id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const ID: &str = "0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn migrates_pasted_urls_in_v0_state() {
+        let state = json!({
+            "databases": [
+                { "name": "链接", "notion_database_id": format!("https://www.notion.so/ws/Calendar-{}?v=fedcba9876543210fedcba9876543210", ID) },
+                { "name": "带连字符", "notion_database_id": "01234567-89ab-cdef-0123-456789abcdef" },
+                { "name": "未连接", "notion_database_id": "" }
+            ]
+        });
+
+        let migrated = migrate_state(state).unwrap();
+        assert_eq!(migrated[VERSION_FIELD], json!(STATE_VERSION));
+        assert_eq!(migrated["databases"][0]["notion_database_id"], json!(ID));
+        assert_eq!(migrated["databases"][1]["notion_database_id"], json!(ID));
+        assert_eq!(migrated["databases"][2]["notion_database_id"], json!(""));
+    }
+
+    #[test]
+    fn leaves_current_state_untouched() {
+        // 当前版本的数据不会再跑迁移，即使内容看起来像旧数据
+        let state = json!({
+            "version": STATE_VERSION,
+            "databases": [{ "name": "日历", "notion_database_id": format!("https://www.notion.so/{}", ID) }]
+        });
+        assert_eq!(migrate_state(state.clone()), Ok(state));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let state = json!({ "version": STATE_VERSION + 1, "databases": [] });
+        assert!(migrate_state(state).is_err());
+    }
+}
@@ -1,17 +1,74 @@
+use crate::store::{History, Trash};
 use crate::types::AppState;
-use gloo_storage::{LocalStorage, Storage};
+use gloo_storage::{LocalStorage, SessionStorage, Storage};
+
+pub mod date_grid;
+mod migrations;
+pub mod navigation;
+mod notion_url;
+pub mod quick_add;
+pub mod shortcuts;
+
+use migrations::{migrate_state, set_version};
+pub use notion_url::parse_notion_url;
 
 const STORAGE_KEY: &str = "notion-cafe-state";
+/// 读取失败时保留原始数据，避免被默认状态覆盖
+const STORAGE_BACKUP_KEY: &str = "notion-cafe-state-backup";
+const HISTORY_KEY: &str = "notion-cafe-history";
+const TRASH_KEY: &str = "notion-cafe-trash";
 
-/// 保存应用状态到本地存储
+/// 保存应用状态到本地存储，带上格式版本号
 pub fn save_state(state: &AppState) -> Result<(), String> {
-    let json = serde_json::to_string(state).map_err(|e| format!("序列化失败: {}", e))?;
-    LocalStorage::set(STORAGE_KEY, json).map_err(|e| format!("存储失败: {:?}", e))
+    let mut value = serde_json::to_value(state).map_err(|e| format!("序列化失败: {}", e))?;
+    set_version(&mut value);
+    LocalStorage::set(STORAGE_KEY, value.to_string()).map_err(|e| format!("存储失败: {:?}", e))
 }
 
-/// 从本地存储加载应用状态
+/// 从本地存储加载应用状态，旧版本的数据先迁移到当前格式
+///
+/// 无法读取时把原始数据另存一份再返回 None，之后保存的默认状态不会让它丢失。
 pub fn load_state() -> Option<AppState> {
     let json: String = LocalStorage::get(STORAGE_KEY).ok()?;
+
+    let result = serde_json::from_str(&json)
+        .map_err(|e| format!("解析失败: {}", e))
+        .and_then(migrate_state)
+        .and_then(|value| serde_json::from_value(value).map_err(|e| format!("解析失败: {}", e)));
+
+    match result {
+        Ok(state) => Some(state),
+        Err(e) => {
+            log::error!("读取保存的状态失败，已备份原始数据: {}", e);
+            if let Err(e) = LocalStorage::set(STORAGE_BACKUP_KEY, json) {
+                log::error!("备份状态失败: {:?}", e);
+            }
+            None
+        }
+    }
+}
+
+/// 保存撤销历史到会话存储（关闭标签页即失效）
+pub fn save_history(history: &History) -> Result<(), String> {
+    let json = serde_json::to_string(history).map_err(|e| format!("序列化失败: {}", e))?;
+    SessionStorage::set(HISTORY_KEY, json).map_err(|e| format!("存储失败: {:?}", e))
+}
+
+/// 从会话存储加载撤销历史
+pub fn load_history() -> Option<History> {
+    let json: String = SessionStorage::get(HISTORY_KEY).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// 保存回收站记录到本地存储
+pub fn save_trash(trash: &Trash) -> Result<(), String> {
+    let json = serde_json::to_string(trash).map_err(|e| format!("序列化失败: {}", e))?;
+    LocalStorage::set(TRASH_KEY, json).map_err(|e| format!("存储失败: {:?}", e))
+}
+
+/// 从本地存储加载回收站记录
+pub fn load_trash() -> Option<Trash> {
+    let json: String = LocalStorage::get(TRASH_KEY).ok()?;
     serde_json::from_str(&json).ok()
 }
 
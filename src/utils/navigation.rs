@@ -0,0 +1,242 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::types::{ViewMode, WeekNumbering};
+
+/// 某月的天数
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+/// 指定年月中的同一天，超出当月天数时取月末
+pub fn clamp_to_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    let day = day.min(days_in_month(year, month));
+    NaiveDate::from_ymd_opt(year, month, day).expect("日期已按当月天数裁剪")
+}
+
+/// 前后移动若干个自然月，保持日期不变，超出时取月末（1 月 31 日 +1 月 = 2 月末）
+pub fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let total = date.year() * 12 + date.month0() as i32 + months;
+    clamp_to_month(
+        total.div_euclid(12),
+        total.rem_euclid(12) as u32 + 1,
+        date.day(),
+    )
+}
+
+/// 日期所在一周的第一天，一周从 `first_day` 开始
+pub fn week_start(date: NaiveDate, first_day: Weekday) -> NaiveDate {
+    date - Duration::days(days_after(date.weekday(), first_day))
+}
+
+/// `weekday` 在 `from` 之后第几天（0-6）
+fn days_after(weekday: Weekday, from: Weekday) -> i64 {
+    (weekday.num_days_from_monday() as i64 - from.num_days_from_monday() as i64).rem_euclid(7)
+}
+
+/// 从 `row_start` 开始的一行七天的周数
+///
+/// 一周的开始与计算方式不一致时（例如周日开始的一行用 ISO 周数），
+/// 取与这一行重叠最多的那一周。
+pub fn week_number(row_start: NaiveDate, numbering: WeekNumbering) -> u32 {
+    let mut offset = days_after(numbering.first_day(), row_start.weekday());
+    if offset > 3 {
+        offset -= 7;
+    }
+    let first = row_start + Duration::days(offset);
+    match numbering {
+        WeekNumbering::Iso => first.iso_week().week(),
+        WeekNumbering::Us => {
+            // 包含 1 月 1 日的那一周为第 1 周，所以按这一周的最后一天（周六）所在的年份计
+            let last = first + Duration::days(6);
+            let new_year = NaiveDate::from_ymd_opt(last.year(), 1, 1).expect("1 月 1 日总是有效的");
+            (last.ordinal0() + new_year.weekday().num_days_from_sunday()) / 7 + 1
+        }
+    }
+}
+
+/// 按视图前后翻页：年视图按 12 个月，月视图和时间线按自然月，周视图和日程按 7 天，日视图按 1 天
+pub fn step(date: NaiveDate, view: ViewMode, steps: i32) -> NaiveDate {
+    match view {
+        ViewMode::Year => add_months(date, steps * 12),
+        ViewMode::Month | ViewMode::Timeline => add_months(date, steps),
+        ViewMode::Week | ViewMode::Agenda => date + Duration::weeks(steps as i64),
+        ViewMode::Day => date + Duration::days(steps as i64),
+    }
+}
+
+/// 跳转到指定年月，保持当前是几号
+pub fn jump_to_month(date: NaiveDate, year: i32, month: u32) -> NaiveDate {
+    clamp_to_month(year, month.clamp(1, 12), date.day())
+}
+
+/// 解析 `<input type="month">` 的值（YYYY-MM）
+pub fn parse_year_month(value: &str) -> Option<(i32, u32)> {
+    let (year, month) = value.split_once('-')?;
+    let year = year.parse().ok()?;
+    let month = month.parse().ok().filter(|m| (1..=12).contains(m))?;
+    Some((year, month))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn counts_days_in_month() {
+        assert_eq!(days_in_month(2026, 1), 31);
+        assert_eq!(days_in_month(2026, 4), 30);
+        assert_eq!(days_in_month(2026, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2026, 12), 31);
+    }
+
+    #[test]
+    fn month_end_clamps_instead_of_skipping() {
+        assert_eq!(add_months(date(2026, 1, 31), 1), date(2026, 2, 28));
+        assert_eq!(add_months(date(2024, 1, 31), 1), date(2024, 2, 29));
+        assert_eq!(add_months(date(2026, 3, 31), -1), date(2026, 2, 28));
+        assert_eq!(add_months(date(2026, 5, 31), 1), date(2026, 6, 30));
+        assert_eq!(add_months(date(2026, 8, 31), 1), date(2026, 9, 30));
+    }
+
+    #[test]
+    fn leap_day_moves_to_february_end() {
+        assert_eq!(add_months(date(2024, 2, 29), 12), date(2025, 2, 28));
+        assert_eq!(add_months(date(2024, 2, 29), 48), date(2028, 2, 29));
+        assert_eq!(add_months(date(2024, 2, 29), 1), date(2024, 3, 29));
+        assert_eq!(add_months(date(2024, 3, 31), -1), date(2024, 2, 29));
+    }
+
+    #[test]
+    fn crosses_year_boundaries() {
+        assert_eq!(add_months(date(2026, 12, 15), 1), date(2027, 1, 15));
+        assert_eq!(add_months(date(2026, 1, 15), -1), date(2025, 12, 15));
+        assert_eq!(add_months(date(2026, 1, 31), -2), date(2025, 11, 30));
+        assert_eq!(add_months(date(2026, 6, 10), 25), date(2028, 7, 10));
+        assert_eq!(add_months(date(2026, 6, 10), -30), date(2023, 12, 10));
+        assert_eq!(add_months(date(2026, 6, 10), 0), date(2026, 6, 10));
+    }
+
+    #[test]
+    fn every_month_is_visited() {
+        let mut current = date(2026, 1, 31);
+        let mut months = Vec::new();
+        for _ in 0..12 {
+            months.push(current.month());
+            current = step(current, ViewMode::Month, 1);
+        }
+        assert_eq!(months, (1..=12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn steps_weeks_and_days() {
+        assert_eq!(step(date(2026, 2, 26), ViewMode::Week, 1), date(2026, 3, 5));
+        assert_eq!(
+            step(date(2026, 1, 3), ViewMode::Week, -1),
+            date(2025, 12, 27)
+        );
+        assert_eq!(step(date(2024, 2, 28), ViewMode::Day, 1), date(2024, 2, 29));
+        assert_eq!(step(date(2026, 2, 28), ViewMode::Day, 1), date(2026, 3, 1));
+        assert_eq!(
+            step(date(2026, 1, 1), ViewMode::Day, -1),
+            date(2025, 12, 31)
+        );
+        assert_eq!(
+            step(date(2024, 2, 29), ViewMode::Year, 1),
+            date(2025, 2, 28)
+        );
+    }
+
+    #[test]
+    fn finds_week_start() {
+        assert_eq!(week_start(date(2026, 3, 9), Weekday::Mon), date(2026, 3, 9));
+        assert_eq!(
+            week_start(date(2026, 3, 15), Weekday::Mon),
+            date(2026, 3, 9)
+        );
+        assert_eq!(
+            week_start(date(2026, 1, 1), Weekday::Mon),
+            date(2025, 12, 29)
+        );
+    }
+
+    #[test]
+    fn finds_week_start_on_sunday_and_saturday() {
+        // 2026-03-11 是周三
+        assert_eq!(
+            week_start(date(2026, 3, 11), Weekday::Sun),
+            date(2026, 3, 8)
+        );
+        assert_eq!(week_start(date(2026, 3, 8), Weekday::Sun), date(2026, 3, 8));
+        assert_eq!(
+            week_start(date(2026, 3, 11), Weekday::Sat),
+            date(2026, 3, 7)
+        );
+        assert_eq!(
+            week_start(date(2026, 3, 13), Weekday::Sat),
+            date(2026, 3, 7)
+        );
+        assert_eq!(
+            week_start(date(2026, 3, 14), Weekday::Sat),
+            date(2026, 3, 14)
+        );
+    }
+
+    #[test]
+    fn numbers_iso_weeks() {
+        assert_eq!(week_number(date(2026, 3, 9), WeekNumbering::Iso), 11);
+        // 2020-12-28 所在的一周包含 2021 年 1 月 1 日，但周四在 2020 年，是第 53 周
+        assert_eq!(week_number(date(2020, 12, 28), WeekNumbering::Iso), 53);
+        assert_eq!(week_number(date(2025, 12, 29), WeekNumbering::Iso), 1);
+        // 周日开始的一行按其中的周一计
+        assert_eq!(week_number(date(2026, 3, 8), WeekNumbering::Iso), 11);
+        // 周六开始的一行按其中的周一计
+        assert_eq!(week_number(date(2026, 3, 7), WeekNumbering::Iso), 11);
+    }
+
+    #[test]
+    fn numbers_us_weeks() {
+        // 包含 2026 年 1 月 1 日（周四）的一周从 2025-12-28 开始
+        assert_eq!(week_number(date(2025, 12, 28), WeekNumbering::Us), 1);
+        assert_eq!(week_number(date(2026, 1, 4), WeekNumbering::Us), 2);
+        assert_eq!(week_number(date(2026, 3, 8), WeekNumbering::Us), 11);
+        assert_eq!(week_number(date(2026, 12, 27), WeekNumbering::Us), 1);
+        assert_eq!(week_number(date(2026, 12, 20), WeekNumbering::Us), 52);
+        // 周一开始的一行按前一天的周日计
+        assert_eq!(week_number(date(2026, 3, 9), WeekNumbering::Us), 11);
+    }
+
+    #[test]
+    fn jumps_to_month_keeping_day() {
+        assert_eq!(jump_to_month(date(2026, 1, 31), 2024, 2), date(2024, 2, 29));
+        assert_eq!(
+            jump_to_month(date(2026, 1, 15), 2030, 11),
+            date(2030, 11, 15)
+        );
+        assert_eq!(jump_to_month(date(2026, 3, 31), 2026, 4), date(2026, 4, 30));
+    }
+
+    #[test]
+    fn parses_month_input() {
+        assert_eq!(parse_year_month("2026-03"), Some((2026, 3)));
+        assert_eq!(parse_year_month("1999-12"), Some((1999, 12)));
+        assert_eq!(parse_year_month("2026-13"), None);
+        assert_eq!(parse_year_month("2026-00"), None);
+        assert_eq!(parse_year_month(""), None);
+        assert_eq!(parse_year_month("2026"), None);
+    }
+}
@@ -0,0 +1,212 @@
+/// 从 Notion 链接或 ID 中解析出的 ID，均为 32 位小写十六进制、不含连字符
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotionIds {
+    pub database_id: String,
+    /// 链接中 `?v=` 指定的视图
+    pub view_id: Option<String>,
+}
+
+/// 解析用户粘贴的数据库链接或 ID
+///
+/// 支持：
+/// - 32 位 ID、带连字符的 UUID
+/// - `notion.so/<id>`、`notion.so/<workspace>/<标题>-<id>`，可带 `?v=` / `&p=` / `#`
+/// - `<workspace>.notion.site/...` 公开链接
+/// - 省略协议的链接和 `notion://` 应用链接
+pub fn parse_notion_url(input: &str) -> Option<NotionIds> {
+    let input = input.trim();
+    if input.is_empty() {
+        return None;
+    }
+
+    // 去掉锚点，分开路径和查询参数
+    let input = input.split('#').next().unwrap_or_default();
+    let (path, query) = match input.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (input, None),
+    };
+
+    let segment = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let database_id = trailing_id(segment)?;
+
+    let view_id = query.and_then(|query| {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "v")
+            .and_then(|(_, value)| trailing_id(value))
+    });
+
+    Some(NotionIds {
+        database_id,
+        view_id,
+    })
+}
+
+/// 取出片段末尾的 32 位 ID，片段可以是 `<标题>-<id>` 或带连字符的 UUID
+fn trailing_id(segment: &str) -> Option<String> {
+    // 从末尾往前取 32 个十六进制字符，跳过 UUID 中的连字符
+    let mut hex = Vec::with_capacity(32);
+    let mut id_start = segment.len();
+    for (index, c) in segment.char_indices().rev() {
+        if hex.len() == 32 {
+            break;
+        }
+        if c == '-' {
+            continue;
+        }
+        if !c.is_ascii_hexdigit() {
+            return None;
+        }
+        hex.push(c);
+        id_start = index;
+    }
+    if hex.len() < 32 {
+        return None;
+    }
+
+    // 前面有标题时必须以连字符隔开，避免从更长的十六进制串中截取
+    let prefix = &segment[..id_start];
+    if !prefix.is_empty() && !prefix.ends_with('-') {
+        return None;
+    }
+
+    Some(hex.iter().rev().collect::<String>().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "0123456789abcdef0123456789abcdef";
+    const VIEW: &str = "fedcba9876543210fedcba9876543210";
+
+    fn ids(view_id: Option<&str>) -> Option<NotionIds> {
+        Some(NotionIds {
+            database_id: ID.to_string(),
+            view_id: view_id.map(str::to_string),
+        })
+    }
+
+    #[test]
+    fn parses_bare_ids() {
+        assert_eq!(parse_notion_url(ID), ids(None));
+        assert_eq!(parse_notion_url(&ID.to_uppercase()), ids(None));
+        assert_eq!(
+            parse_notion_url("  0123456789abcdef0123456789abcdef\n"),
+            ids(None)
+        );
+        assert_eq!(
+            parse_notion_url("01234567-89ab-cdef-0123-456789abcdef"),
+            ids(None)
+        );
+    }
+
+    #[test]
+    fn parses_notion_so_links() {
+        let cases = [
+            format!("https://www.notion.so/{}", ID),
+            format!("https://www.notion.so/{}/", ID),
+            format!("https://notion.so/{}", ID),
+            format!("https://www.notion.so/workspace/{}", ID),
+            format!("https://www.notion.so/workspace/My-Calendar-{}", ID),
+            format!("https://www.notion.so/My-Calendar-{}", ID),
+            "https://www.notion.so/workspace/01234567-89ab-cdef-0123-456789abcdef".to_string(),
+            format!("www.notion.so/workspace/Tasks-{}", ID),
+            format!("notion.so/{}", ID),
+            format!("notion://www.notion.so/workspace/Tasks-{}", ID),
+            format!("https://www.notion.so/{}#section", ID),
+        ];
+        for case in &cases {
+            assert_eq!(parse_notion_url(case), ids(None), "{}", case);
+        }
+    }
+
+    #[test]
+    fn parses_view_ids() {
+        let cases = [
+            format!("https://www.notion.so/{}?v={}", ID, VIEW),
+            format!("https://www.notion.so/workspace/My-DB-{}?v={}", ID, VIEW),
+            format!(
+                "https://www.notion.so/workspace/{}?v={}&p=abc&pm=s",
+                ID, VIEW
+            ),
+            format!("https://www.notion.so/workspace/{}?pvs=4&v={}", ID, VIEW),
+            format!(
+                "https://www.notion.so/{}?v=fedcba98-7654-3210-fedc-ba9876543210",
+                ID
+            ),
+            format!("https://www.notion.so/{}?v={}#top", ID, VIEW.to_uppercase()),
+        ];
+        for case in &cases {
+            assert_eq!(parse_notion_url(case), ids(Some(VIEW)), "{}", case);
+        }
+    }
+
+    #[test]
+    fn ignores_invalid_view_ids() {
+        assert_eq!(
+            parse_notion_url(&format!("https://www.notion.so/{}?v=abc", ID)),
+            ids(None)
+        );
+        assert_eq!(
+            parse_notion_url(&format!("https://www.notion.so/{}?view={}", ID, VIEW)),
+            ids(None)
+        );
+    }
+
+    #[test]
+    fn parses_notion_site_links() {
+        let cases = [
+            format!("https://workspace.notion.site/{}", ID),
+            format!("https://workspace.notion.site/Public-Calendar-{}", ID),
+            format!("https://workspace.notion.site/{}?v={}", ID, VIEW),
+            format!(
+                "https://workspace.notion.site/Public-Calendar-{}?v={}&pvs=4",
+                ID, VIEW
+            ),
+        ];
+        for case in &cases {
+            assert_eq!(
+                parse_notion_url(case).map(|ids| ids.database_id),
+                Some(ID.to_string()),
+                "{}",
+                case
+            );
+        }
+    }
+
+    #[test]
+    fn handles_titles_with_unicode_and_hex_like_words() {
+        assert_eq!(
+            parse_notion_url(&format!("https://www.notion.so/ws/日历-{}", ID)),
+            ids(None)
+        );
+        assert_eq!(
+            parse_notion_url(&format!("https://www.notion.so/ws/Cafe-Bead-{}", ID)),
+            ids(None)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let cases = [
+            "",
+            "   ",
+            "not a url",
+            "0123456789abcdef",
+            "0123456789abcdef0123456789abcdeg",
+            "https://www.notion.so/",
+            "https://www.notion.so/workspace/Calendar",
+            // 33 位十六进制，没有连字符分隔标题
+            "https://www.notion.so/a0123456789abcdef0123456789abcdef",
+        ];
+        for case in cases {
+            assert_eq!(parse_notion_url(case), None, "{:?}", case);
+        }
+    }
+}
@@ -0,0 +1,1068 @@
+//! 快速添加：把“明天下午3点 周会”“every Monday standup”这样的短句
+//! 拆成标题、日期、时间和重复规则。
+//!
+//! 依次从文本中找出重复规则、日期（或日期范围）和时间（或时间范围），
+//! 每找到一处就从文本中去掉，剩下的部分作为标题。
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+
+use super::navigation::{add_months, week_start};
+use super::weekday_name;
+
+/// 重复规则
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recurrence {
+    Daily,
+    /// 周一到周五
+    Weekdays,
+    Weekly(Weekday),
+    /// 每月的第几天，当月没有这一天时跳过
+    Monthly(u32),
+}
+
+impl Recurrence {
+    pub fn matches(&self, date: NaiveDate) -> bool {
+        match self {
+            Recurrence::Daily => true,
+            Recurrence::Weekdays => date.weekday().num_days_from_monday() < 5,
+            Recurrence::Weekly(weekday) => date.weekday() == *weekday,
+            Recurrence::Monthly(day) => date.day() == *day,
+        }
+    }
+
+    /// 从 `start`（含）开始的前 `count` 次
+    pub fn occurrences(&self, start: NaiveDate, count: usize) -> Vec<NaiveDate> {
+        start
+            .iter_days()
+            .filter(|date| self.matches(*date))
+            .take(count)
+            .collect()
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Recurrence::Daily => "每天".to_string(),
+            Recurrence::Weekdays => "每个工作日".to_string(),
+            Recurrence::Weekly(weekday) => format!("每{}", weekday_name(*weekday)),
+            Recurrence::Monthly(day) => format!("每月{}号", day),
+        }
+    }
+}
+
+/// 解析结果；没有写日期时为今天，有重复规则时为第一次发生的日期
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+    pub recurrence: Option<Recurrence>,
+}
+
+/// 解析快速添加的文本，去掉日期和时间后没有剩下标题时返回 None
+pub fn parse_quick_add(input: &str, today: NaiveDate) -> Option<QuickAdd> {
+    let mut text = normalize(input);
+
+    let recurrence = extract(&mut text, recurrence);
+    let dates = extract(&mut text, |c| date_range(c, today));
+    let times = extract(&mut text, time_range);
+
+    let title = clean_title(&text);
+    if title.is_empty() {
+        return None;
+    }
+
+    let (mut start_date, mut end_date) = dates.unwrap_or((today, None));
+    if let Some(recurrence) = recurrence {
+        start_date = recurrence.occurrences(start_date, 1)[0];
+        end_date = None;
+    }
+    let (start_time, end_time) = match times {
+        Some((start, end)) => (Some(start), end),
+        None => (None, None),
+    };
+
+    Some(QuickAdd {
+        title,
+        start_date,
+        end_date,
+        start_time,
+        end_time,
+        recurrence,
+    })
+}
+
+/// 统一全角符号和空白，把“今晚”这类缩写展开成日期加时段
+fn normalize(input: &str) -> String {
+    let text: String = input
+        .chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            '：' => ':',
+            '～' => '~',
+            '－' => '-',
+            _ => c,
+        })
+        .collect();
+    let text = text
+        .replace("今晚", "今天晚上")
+        .replace("明晚", "明天晚上")
+        .replace("明早", "明天早上");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 去掉多余的空白和两端的标点
+fn clean_title(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| " ,，、。.;；:-–~".contains(c))
+        .to_string()
+}
+
+/// 找到第一处能解析的文本，把它从 `text` 中去掉并返回解析结果
+///
+/// 以英文字母或数字开头（结尾）的匹配，前面（后面）不能紧接着字母或数字，
+/// 避免从单词或更长的数字中间开始匹配。
+fn extract<T>(text: &mut String, parse: impl Fn(&mut Cursor) -> Option<T>) -> Option<T> {
+    let found = text.char_indices().find_map(|(start, _)| {
+        let mut cursor = Cursor {
+            text: text.as_str(),
+            pos: start,
+        };
+        let value = parse(&mut cursor)?;
+        let end = cursor.pos;
+        (end > start && at_word_boundary(text, start, end)).then_some((start, end, value))
+    });
+    let (start, end, value) = found?;
+    text.replace_range(start..end, " ");
+    Some(value)
+}
+
+fn at_word_boundary(text: &str, start: usize, end: usize) -> bool {
+    let alphanumeric = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+    let before = text[..start].chars().next_back();
+    let first = text[start..].chars().next();
+    let last = text[..end].chars().next_back();
+    let after = text[end..].chars().next();
+    let joins_start = alphanumeric(before) && alphanumeric(first);
+    let joins_end = alphanumeric(last) && alphanumeric(after);
+    !joins_start && !joins_end
+}
+
+// ========== 文本游标 ==========
+#[derive(Clone, Copy)]
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    /// 匹配字面量，英文不区分大小写
+    fn eat(&mut self, literal: &str) -> bool {
+        match self.rest().get(..literal.len()) {
+            Some(prefix) if prefix.eq_ignore_ascii_case(literal) => {
+                self.pos += literal.len();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 依次尝试，返回匹配到的下标；有包含关系时较长的要放在前面
+    fn eat_any(&mut self, literals: &[&str]) -> Option<usize> {
+        literals.iter().position(|literal| self.eat(literal))
+    }
+
+    /// 匹配英文单词，后面不能紧接着字母或数字，也不能是“Monday.com”这样的词中的点
+    fn eat_word(&mut self, word: &str) -> bool {
+        let start = self.pos;
+        if self.eat(word) {
+            let rest = self.rest().strip_prefix('.').unwrap_or(self.rest());
+            if !rest.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+                return true;
+            }
+        }
+        self.pos = start;
+        false
+    }
+
+    fn eat_any_word(&mut self, words: &[&str]) -> Option<usize> {
+        words.iter().position(|word| self.eat_word(word))
+    }
+
+    fn spaces(&mut self) {
+        self.pos = self.text.len() - self.rest().trim_start().len();
+    }
+
+    /// 不超过 `max` 位的阿拉伯数字
+    fn digits(&mut self, max: usize) -> Option<u32> {
+        let len = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 || len > max {
+            return None;
+        }
+        let value = self.rest()[..len].parse().ok()?;
+        self.pos += len;
+        Some(value)
+    }
+
+    /// 两位以内的阿拉伯数字或中文数字
+    fn number(&mut self) -> Option<u32> {
+        if let Some(value) = self.digits(2) {
+            return Some(value);
+        }
+        let run: String = self
+            .rest()
+            .chars()
+            .take_while(|c| chinese_digit(*c).is_some() || *c == '十')
+            .collect();
+        let value = parse_chinese_number(&run)?;
+        self.pos += run.len();
+        Some(value)
+    }
+
+    /// 解析失败时回到原来的位置
+    fn attempt<T>(&mut self, parse: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let saved = *self;
+        let value = parse(self);
+        if value.is_none() {
+            *self = saved;
+        }
+        value
+    }
+}
+
+fn chinese_digit(c: char) -> Option<u32> {
+    "零一二三四五六七八九"
+        .chars()
+        .position(|d| d == c)
+        .map(|d| d as u32)
+        .or(match c {
+            '〇' => Some(0),
+            '两' => Some(2),
+            _ => None,
+        })
+}
+
+/// 一到九十九：“三”“十二”“二十”“三十一”
+fn parse_chinese_number(text: &str) -> Option<u32> {
+    let single = |s: &str| -> Option<u32> {
+        let mut chars = s.chars();
+        let digit = chinese_digit(chars.next()?)?;
+        chars.next().is_none().then_some(digit)
+    };
+    match text.split_once('十') {
+        Some((tens, units)) => {
+            let tens = if tens.is_empty() { 1 } else { single(tens)? };
+            let units = if units.is_empty() { 0 } else { single(units)? };
+            Some(tens * 10 + units)
+        }
+        None => single(text),
+    }
+}
+
+// ========== 重复 ==========
+fn recurrence(c: &mut Cursor) -> Option<Recurrence> {
+    if c.eat_any(&["每天", "每日", "天天"]).is_some()
+        || c.eat_any_word(&["every day", "everyday", "daily"])
+            .is_some()
+    {
+        return Some(Recurrence::Daily);
+    }
+    if c.eat_any(&["每个工作日", "每工作日"]).is_some()
+        || c.eat_any_word(&["every weekday", "weekdays"]).is_some()
+    {
+        return Some(Recurrence::Weekdays);
+    }
+    c.attempt(|c| {
+        c.eat_any(&["每个星期", "每星期", "每个礼拜", "每礼拜", "每周"])?;
+        chinese_weekday(c).map(Recurrence::Weekly)
+    })
+    .or_else(|| {
+        c.attempt(|c| {
+            c.eat_any(&["每个月", "每月"])?;
+            let day = c.number()?;
+            c.eat_any(&["号", "日"])?;
+            (1..=31).contains(&day).then_some(Recurrence::Monthly(day))
+        })
+    })
+    .or_else(|| {
+        c.attempt(|c| {
+            c.eat_word("every").then_some(())?;
+            c.spaces();
+            english_weekday(c).map(Recurrence::Weekly)
+        })
+    })
+}
+
+// ========== 日期 ==========
+/// 日期范围的分隔符
+const RANGE_SEPARATORS: [&str; 8] = ["-", "–", "—", "~", "到", "至", "through", "to"];
+
+const CHINESE_WEEKDAYS: [(&str, Weekday); 8] = [
+    ("一", Weekday::Mon),
+    ("二", Weekday::Tue),
+    ("三", Weekday::Wed),
+    ("四", Weekday::Thu),
+    ("五", Weekday::Fri),
+    ("六", Weekday::Sat),
+    ("日", Weekday::Sun),
+    ("天", Weekday::Sun),
+];
+
+const ENGLISH_WEEKDAYS: [(&str, Weekday); 17] = [
+    ("monday", Weekday::Mon),
+    ("mon", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("tues", Weekday::Tue),
+    ("tue", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("wed", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("thurs", Weekday::Thu),
+    ("thu", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("fri", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sat", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+    ("sun", Weekday::Sun),
+    ("weekend", Weekday::Sat),
+];
+
+const ENGLISH_MONTHS: [(&str, u32); 24] = [
+    ("january", 1),
+    ("jan", 1),
+    ("february", 2),
+    ("feb", 2),
+    ("march", 3),
+    ("mar", 3),
+    ("april", 4),
+    ("apr", 4),
+    ("may", 5),
+    ("june", 6),
+    ("jun", 6),
+    ("july", 7),
+    ("jul", 7),
+    ("august", 8),
+    ("aug", 8),
+    ("september", 9),
+    ("sept", 9),
+    ("sep", 9),
+    ("october", 10),
+    ("oct", 10),
+    ("november", 11),
+    ("nov", 11),
+    ("december", 12),
+    ("dec", 12),
+];
+
+fn chinese_weekday(c: &mut Cursor) -> Option<Weekday> {
+    let names = CHINESE_WEEKDAYS.map(|(name, _)| name);
+    c.eat_any(&names).map(|i| CHINESE_WEEKDAYS[i].1)
+}
+
+fn english_weekday(c: &mut Cursor) -> Option<Weekday> {
+    let names = ENGLISH_WEEKDAYS.map(|(name, _)| name);
+    c.eat_any_word(&names).map(|i| ENGLISH_WEEKDAYS[i].1)
+}
+
+fn english_month(c: &mut Cursor) -> Option<u32> {
+    let names = ENGLISH_MONTHS.map(|(name, _)| name);
+    let month = c.eat_any_word(&names).map(|i| ENGLISH_MONTHS[i].1)?;
+    c.eat(".");
+    Some(month)
+}
+
+/// 12、12th
+fn english_day(c: &mut Cursor) -> Option<u32> {
+    let day = c.digits(2)?;
+    c.eat_any(&["st", "nd", "rd", "th"]);
+    Some(day)
+}
+
+/// 没有写年份的日期：今年的已经过去时取明年
+fn upcoming_date(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    let date = NaiveDate::from_ymd_opt(today.year(), month, day)?;
+    if date < today {
+        NaiveDate::from_ymd_opt(today.year() + 1, month, day)
+    } else {
+        Some(date)
+    }
+}
+
+/// 今天或之后最近的星期几
+fn upcoming_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let ahead = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(ahead as i64)
+}
+
+/// 本周（0）、下周（1）……的星期几；“这周”“下周”按中文习惯从周一算起
+fn weekday_of_week(today: NaiveDate, weeks: i64, weekday: Weekday) -> NaiveDate {
+    week_start(today, Weekday::Mon)
+        + Duration::weeks(weeks)
+        + Duration::days(weekday.num_days_from_monday() as i64)
+}
+
+/// 范围的结束日期；没写月份时与开始同月，写了更早的月份时是跨年
+fn range_end(start: NaiveDate, month: Option<u32>, day: u32) -> Option<NaiveDate> {
+    let end = NaiveDate::from_ymd_opt(start.year(), month.unwrap_or(start.month()), day)?;
+    if end >= start {
+        Some(end)
+    } else if month.is_some() {
+        NaiveDate::from_ymd_opt(start.year() + 1, end.month(), day)
+    } else {
+        None
+    }
+}
+
+type DateParser = fn(&mut Cursor, NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)>;
+
+fn date_range(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    const PARSERS: [DateParser; 8] = [
+        iso_date,
+        chinese_date,
+        english_date,
+        slash_date,
+        relative_day,
+        chinese_week,
+        english_week,
+        chinese_day_of_month,
+    ];
+    PARSERS
+        .iter()
+        .find_map(|parse| c.attempt(|c| parse(c, today)))
+}
+
+/// 2026-03-12
+fn iso_date(c: &mut Cursor, _today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let year = c.digits(4)?;
+    c.eat("-").then_some(())?;
+    let month = c.digits(2)?;
+    c.eat("-").then_some(())?;
+    let day = c.digits(2)?;
+    Some((NaiveDate::from_ymd_opt(year as i32, month, day)?, None))
+}
+
+/// 2026年3月12日、3月12号、3月12日到14日、3月30日-4月2日
+fn chinese_date(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let year = c.attempt(|c| {
+        let year = c.digits(4)?;
+        c.eat("年").then_some(year as i32)
+    });
+    let month = c.number()?;
+    c.eat("月").then_some(())?;
+    let day = c.number()?;
+    c.eat_any(&["日", "号"]);
+
+    let start = match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day)?,
+        None => upcoming_date(today, month, day)?,
+    };
+    let end = c.attempt(|c| {
+        c.spaces();
+        c.eat_any(&RANGE_SEPARATORS)?;
+        c.spaces();
+        let month = c.attempt(|c| {
+            let month = c.number()?;
+            c.eat("月").then_some(month)
+        });
+        let day = c.number()?;
+        c.eat_any(&["日", "号"]);
+        range_end(start, month, day)
+    });
+    Some((start, end))
+}
+
+/// Mar 12、March 12th、Mar 12-14、Dec 30 - Jan 2
+fn english_date(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let month = english_month(c)?;
+    c.spaces();
+    let day = english_day(c)?;
+    let start = upcoming_date(today, month, day)?;
+    let end = c.attempt(|c| {
+        c.spaces();
+        c.eat_any(&RANGE_SEPARATORS)?;
+        c.spaces();
+        let month = c.attempt(|c| {
+            let month = english_month(c)?;
+            c.spaces();
+            Some(month)
+        });
+        let day = english_day(c)?;
+        range_end(start, month, day)
+    });
+    Some((start, end))
+}
+
+/// 3/12（月/日）
+fn slash_date(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let month = c.digits(2)?;
+    c.eat("/").then_some(())?;
+    let day = c.digits(2)?;
+    Some((upcoming_date(today, month, day)?, None))
+}
+
+/// 今天、明天、后天、tomorrow……
+fn relative_day(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    const CHINESE: [(&str, i64); 6] = [
+        ("大后天", 3),
+        ("后天", 2),
+        ("明天", 1),
+        ("明日", 1),
+        ("今天", 0),
+        ("今日", 0),
+    ];
+    const ENGLISH: [(&str, i64); 5] = [
+        ("day after tomorrow", 2),
+        ("tomorrow", 1),
+        ("tmr", 1),
+        ("today", 0),
+        ("tonight", 0),
+    ];
+    let days = c
+        .eat_any(&CHINESE.map(|(word, _)| word))
+        .map(|i| CHINESE[i].1)
+        .or_else(|| {
+            c.eat_any_word(&ENGLISH.map(|(word, _)| word))
+                .map(|i| ENGLISH[i].1)
+        })?;
+    Some((today + Duration::days(days), None))
+}
+
+/// 周五、星期天（今天或之后最近的一天），这周一、下周五、下下周三
+fn chinese_week(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    const PREFIXES: [(&str, Option<i64>); 15] = [
+        ("下下个星期", Some(2)),
+        ("下下星期", Some(2)),
+        ("下下周", Some(2)),
+        ("下个星期", Some(1)),
+        ("下星期", Some(1)),
+        ("下礼拜", Some(1)),
+        ("下周", Some(1)),
+        ("这个星期", Some(0)),
+        ("这星期", Some(0)),
+        ("这礼拜", Some(0)),
+        ("这周", Some(0)),
+        ("本周", Some(0)),
+        ("星期", None),
+        ("礼拜", None),
+        ("周", None),
+    ];
+    let weeks = c
+        .eat_any(&PREFIXES.map(|(prefix, _)| prefix))
+        .map(|i| PREFIXES[i].1)?;
+    let weekday = chinese_weekday(c)?;
+    let date = match weeks {
+        Some(weeks) => weekday_of_week(today, weeks, weekday),
+        None => upcoming_weekday(today, weekday),
+    };
+    Some((date, None))
+}
+
+/// friday、on fri（今天或之后最近的一天），this monday、next friday（下周的那一天），
+/// Fri Mar 13（以后面的日期为准）
+///
+/// 缩写和 weekend 也常出现在标题里（“Sun Valley trip”），前面没有 on / next / this 时，
+/// 后面必须紧跟日期或时间才算。
+fn english_week(c: &mut Cursor, today: NaiveDate) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let weeks = match c.eat_any_word(&["next", "this", "on"]) {
+        Some(0) => Some(Some(1)),
+        Some(1) => Some(Some(0)),
+        Some(_) => Some(None),
+        None => None,
+    };
+    c.spaces();
+    let word_start = c.pos;
+    let weekday = english_weekday(c)?;
+    // weekend 也按缩写处理
+    let abbreviated = c.pos - word_start != weekday_full_name(weekday).len();
+
+    let following_date = c.attempt(|c| {
+        c.eat(",");
+        c.spaces();
+        [iso_date, english_date, slash_date]
+            .iter()
+            .find_map(|parse| c.attempt(|c| parse(c, today)))
+    });
+    if let Some(date) = following_date {
+        return Some(date);
+    }
+    if weeks.is_none() && abbreviated && !followed_by_time(c) {
+        return None;
+    }
+
+    let date = match weeks.flatten() {
+        Some(weeks) => weekday_of_week(today, weeks, weekday),
+        None => upcoming_weekday(today, weekday),
+    };
+    Some((date, None))
+}
+
+fn weekday_full_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "monday",
+        Weekday::Tue => "tuesday",
+        Weekday::Wed => "wednesday",
+        Weekday::Thu => "thursday",
+        Weekday::Fri => "friday",
+        Weekday::Sat => "saturday",
+        Weekday::Sun => "sunday",
+    }
+}
+
+/// 后面紧跟一个时间或时间范围，不移动游标
+fn followed_by_time(c: &Cursor) -> bool {
+    let mut next = *c;
+    next.spaces();
+    time_range(&mut next).is_some()
+}
+
+/// 15号：本月的还没过时取本月，否则取下个月
+fn chinese_day_of_month(
+    c: &mut Cursor,
+    today: NaiveDate,
+) -> Option<(NaiveDate, Option<NaiveDate>)> {
+    let day = c.number()?;
+    c.eat_any(&["号", "日"])?;
+    let date = if day >= today.day() {
+        today.with_day(day)?
+    } else {
+        add_months(today.with_day(1)?, 1).with_day(day)?
+    };
+    Some((date, None))
+}
+
+// ========== 时间 ==========
+/// 上午、下午等时段，决定 12 小时制的钟点如何换算
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Period {
+    Am,
+    Noon,
+    Pm,
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct TimePoint {
+    hour: u32,
+    minute: u32,
+    period: Period,
+}
+
+impl TimePoint {
+    fn hour_24(&self, period: Period) -> u32 {
+        match period {
+            Period::Am if self.hour == 12 => 0,
+            Period::Pm if self.hour < 12 => self.hour + 12,
+            // 中午 1 点是 13 点，中午 11 点还是上午
+            Period::Noon if self.hour < 11 => self.hour + 12,
+            _ => self.hour,
+        }
+    }
+
+    fn time(&self, period: Period) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(self.hour_24(period), self.minute, 0)
+    }
+}
+
+/// 下午3点、晚上8点半、中午12点、10:30、3pm、3:30 p.m.、noon
+fn time_point(c: &mut Cursor) -> Option<TimePoint> {
+    c.attempt(chinese_time).or_else(|| c.attempt(english_time))
+}
+
+fn chinese_time(c: &mut Cursor) -> Option<TimePoint> {
+    let period = match c.eat_any(&[
+        "凌晨", "早上", "早晨", "上午", "中午", "下午", "傍晚", "晚上", "夜里",
+    ]) {
+        Some(0..=3) => Period::Am,
+        Some(4) => Period::Noon,
+        Some(_) => Period::Pm,
+        None => Period::Unknown,
+    };
+    c.spaces();
+    let hour = c.number()?;
+    let minute = if c.eat_any(&["点", "时"]).is_some() {
+        if c.eat("半") {
+            30
+        } else if c.eat("一刻") {
+            15
+        } else if c.eat("三刻") {
+            45
+        } else {
+            c.attempt(|c| {
+                let minute = c.number()?;
+                c.eat("分");
+                Some(minute)
+            })
+            .unwrap_or(0)
+        }
+    } else if period != Period::Unknown && c.eat(":") {
+        c.digits(2)?
+    } else {
+        return None;
+    };
+    Some(TimePoint {
+        hour,
+        minute,
+        period,
+    })
+}
+
+fn english_time(c: &mut Cursor) -> Option<TimePoint> {
+    if c.eat_word("noon") {
+        return Some(TimePoint {
+            hour: 12,
+            minute: 0,
+            period: Period::Noon,
+        });
+    }
+    if c.eat_word("midnight") {
+        return Some(TimePoint {
+            hour: 0,
+            minute: 0,
+            period: Period::Am,
+        });
+    }
+    let point = bare_time(c)?;
+    let period = c
+        .attempt(|c| {
+            c.spaces();
+            match c.eat_any_word(&["am", "a.m.", "pm", "p.m."])? {
+                0 | 1 => Some(Period::Am),
+                _ => Some(Period::Pm),
+            }
+        })
+        .unwrap_or(Period::Unknown);
+    // 只有一个数字时不能确定是时间
+    if period == Period::Unknown && !point.has_minute {
+        return None;
+    }
+    Some(TimePoint {
+        period,
+        ..point.point
+    })
+}
+
+/// 没有时段的钟点，只能作为“3-5pm”这样的范围的开始
+struct BareTime {
+    point: TimePoint,
+    has_minute: bool,
+}
+
+fn bare_time(c: &mut Cursor) -> Option<BareTime> {
+    let hour = c.digits(2)?;
+    let minute = c.attempt(|c| {
+        c.eat(":").then_some(())?;
+        c.digits(2)
+    });
+    Some(BareTime {
+        point: TimePoint {
+            hour,
+            minute: minute.unwrap_or(0),
+            period: Period::Unknown,
+        },
+        has_minute: minute.is_some(),
+    })
+}
+
+/// 单个时间或时间范围，结束时间没写时段时沿用开始的时段，反之亦然
+fn time_range(c: &mut Cursor) -> Option<(NaiveTime, Option<NaiveTime>)> {
+    c.attempt(|c| {
+        c.eat_any_word(&["at", "from"])?;
+        c.spaces();
+        Some(())
+    });
+
+    let range = c.attempt(|c| {
+        let start = c
+            .attempt(time_point)
+            .or_else(|| c.attempt(bare_time).map(|bare| bare.point))?;
+        c.spaces();
+        c.eat_any(&RANGE_SEPARATORS)?;
+        c.spaces();
+        let end = time_point(c)?;
+
+        let end_period = if end.period == Period::Unknown {
+            start.period
+        } else {
+            end.period
+        };
+        let end_time = end.time(end_period)?;
+        let start_time = if start.period == Period::Unknown {
+            // 3-5pm 是 15 点到 17 点，11-1pm 是 11 点到 13 点
+            start
+                .time(end_period)
+                .filter(|time| *time < end_time)
+                .or_else(|| start.time(Period::Unknown))?
+        } else {
+            start.time(start.period)?
+        };
+        (start_time < end_time).then_some((start_time, Some(end_time)))
+    });
+
+    range.or_else(|| {
+        let point = time_point(c)?;
+        Some((point.time(point.period)?, None))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2026-03-11 是周三
+    fn today() -> NaiveDate {
+        date(2026, 3, 11)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> Option<NaiveTime> {
+        NaiveTime::from_hms_opt(h, m, 0)
+    }
+
+    struct Case {
+        input: &'static str,
+        title: &'static str,
+        start: NaiveDate,
+        end: Option<NaiveDate>,
+        start_time: Option<NaiveTime>,
+        end_time: Option<NaiveTime>,
+        recurrence: Option<Recurrence>,
+    }
+
+    fn case(input: &'static str, title: &'static str, start: NaiveDate) -> Case {
+        Case {
+            input,
+            title,
+            start,
+            end: None,
+            start_time: None,
+            end_time: None,
+            recurrence: None,
+        }
+    }
+
+    impl Case {
+        fn until(mut self, end: NaiveDate) -> Self {
+            self.end = Some(end);
+            self
+        }
+
+        fn at(mut self, h: u32, m: u32) -> Self {
+            self.start_time = time(h, m);
+            self
+        }
+
+        fn to(mut self, h: u32, m: u32) -> Self {
+            self.end_time = time(h, m);
+            self
+        }
+
+        fn every(mut self, recurrence: Recurrence) -> Self {
+            self.recurrence = Some(recurrence);
+            self
+        }
+    }
+
+    #[test]
+    fn parses_phrases() {
+        let cases = [
+            // 请求中的例子
+            case("明天下午3点 周会", "周会", date(2026, 3, 12)).at(15, 0),
+            case("下周五 交报告", "交报告", date(2026, 3, 20)),
+            case("every Monday standup", "standup", date(2026, 3, 16))
+                .every(Recurrence::Weekly(Weekday::Mon)),
+            case("Mar 12-14 offsite", "offsite", date(2026, 3, 12)).until(date(2026, 3, 14)),
+            // 中文相对日期
+            case("今天 写周报", "写周报", today()),
+            case("后天晚上8点 聚餐", "聚餐", date(2026, 3, 13)).at(20, 0),
+            case("大后天 体检", "体检", date(2026, 3, 14)),
+            case("周五 团建", "团建", date(2026, 3, 13)),
+            case("周三 例会", "例会", today()),
+            case("星期天 爬山", "爬山", date(2026, 3, 15)),
+            case("礼拜一 交作业", "交作业", date(2026, 3, 16)),
+            case("这周一 复盘", "复盘", date(2026, 3, 9)),
+            case("本周日 大扫除", "大扫除", date(2026, 3, 15)),
+            case("下星期二 面试", "面试", date(2026, 3, 17)),
+            case("下下周三 出差", "出差", date(2026, 3, 25)),
+            case("今晚7点半 看电影", "看电影", today()).at(19, 30),
+            case("明早8点 晨跑", "晨跑", date(2026, 3, 12)).at(8, 0),
+            // 中文绝对日期
+            case("3月20日 发布", "发布", date(2026, 3, 20)),
+            case("3月11号 今天到期", "今天到期", today()),
+            case("2月1日 年会", "年会", date(2027, 2, 1)),
+            case("2026年5月1日 假期", "假期", date(2026, 5, 1)),
+            case("三月十二日 植树", "植树", date(2026, 3, 12)),
+            case("3月12日到15日 出差", "出差", date(2026, 3, 12)).until(date(2026, 3, 15)),
+            case("3月30日-4月2日 休假", "休假", date(2026, 3, 30)).until(date(2026, 4, 2)),
+            case("15号 交房租", "交房租", date(2026, 3, 15)),
+            case("5号 交房租", "交房租", date(2026, 4, 5)),
+            // 中文时间
+            case("下午3点到5点 培训", "培训", today())
+                .at(15, 0)
+                .to(17, 0),
+            case("上午11点到下午1点 评审", "评审", today())
+                .at(11, 0)
+                .to(13, 0),
+            case("中午12点 午饭", "午饭", today()).at(12, 0),
+            case("中午1点 午休", "午休", today()).at(13, 0),
+            case("凌晨2点 发版", "发版", today()).at(2, 0),
+            case("晚上十点 复习", "复习", today()).at(22, 0),
+            case("两点半 喝茶", "喝茶", today()).at(2, 30),
+            case("9点15分 站会", "站会", today()).at(9, 15),
+            case("10点一刻 电话", "电话", today()).at(10, 15),
+            case("14:00-15:30 评审", "评审", today())
+                .at(14, 0)
+                .to(15, 30),
+            case("明天 14：00 评审", "评审", date(2026, 3, 12)).at(14, 0),
+            // 中文重复
+            case("每天早上9点 站会", "站会", today())
+                .at(9, 0)
+                .every(Recurrence::Daily),
+            case("每周三 例会", "例会", today()).every(Recurrence::Weekly(Weekday::Wed)),
+            case("每周五下午4点 周报", "周报", date(2026, 3, 13))
+                .at(16, 0)
+                .every(Recurrence::Weekly(Weekday::Fri)),
+            case("每月1号 发工资", "发工资", date(2026, 4, 1)).every(Recurrence::Monthly(1)),
+            case("每个工作日 10点 晨会", "晨会", today())
+                .at(10, 0)
+                .every(Recurrence::Weekdays),
+            // 英文相对日期
+            case("tomorrow 3pm dentist", "dentist", date(2026, 3, 12)).at(15, 0),
+            case("call mom on sunday", "call mom", date(2026, 3, 15)),
+            case("friday drinks", "drinks", date(2026, 3, 13)),
+            case("on Wed sync", "sync", today()),
+            case("Wed 10am sync", "sync", today()).at(10, 0),
+            case("sat 2-4pm brunch", "brunch", date(2026, 3, 14))
+                .at(14, 0)
+                .to(16, 0),
+            case("next weekend camping", "camping", date(2026, 3, 21)),
+            case("Fri, Mar 20 dinner", "dinner", date(2026, 3, 20)),
+            case("this monday retro", "retro", date(2026, 3, 9)),
+            case(
+                "next friday 10am-11:30am review",
+                "review",
+                date(2026, 3, 20),
+            )
+            .at(10, 0)
+            .to(11, 30),
+            case("Dentist today at 4:15 PM", "Dentist", today()).at(16, 15),
+            case("day after tomorrow hike", "hike", date(2026, 3, 13)),
+            // 英文绝对日期
+            case("March 20th launch party", "launch party", date(2026, 3, 20)),
+            case("Jan 5 kickoff", "kickoff", date(2027, 1, 5)),
+            case("Dec 30 - Jan 2 vacation", "vacation", date(2026, 12, 30)).until(date(2027, 1, 2)),
+            case("2026-04-01 launch", "launch", date(2026, 4, 1)),
+            case("4/2 demo", "demo", date(2026, 4, 2)),
+            // 英文时间
+            case("lunch with Alex at noon", "lunch with Alex", today()).at(12, 0),
+            case("3-5pm workshop", "workshop", today())
+                .at(15, 0)
+                .to(17, 0),
+            case("11-1pm brunch", "brunch", today()).at(11, 0).to(13, 0),
+            case("standup 9:30", "standup", today()).at(9, 30),
+            case("meeting from 2pm to 3pm", "meeting", today())
+                .at(14, 0)
+                .to(15, 0),
+            case("release at midnight", "release", today()).at(0, 0),
+            case("12am backup", "backup", today()).at(0, 0),
+            case("12pm lunch", "lunch", today()).at(12, 0),
+            // 英文重复
+            case("every day 9am standup", "standup", today())
+                .at(9, 0)
+                .every(Recurrence::Daily),
+            case("daily vitamins", "vitamins", today()).every(Recurrence::Daily),
+            case("every weekday 9:00 standup", "standup", today())
+                .at(9, 0)
+                .every(Recurrence::Weekdays),
+            case("Every Friday 5pm demo", "demo", date(2026, 3, 13))
+                .at(17, 0)
+                .every(Recurrence::Weekly(Weekday::Fri)),
+            // 顺序不固定，没有日期和时间
+            case("下午3点 周会 明天", "周会", date(2026, 3, 12)).at(15, 0),
+            case("买牛奶", "买牛奶", today()),
+            case("read 3 chapters", "read 3 chapters", today()),
+            case("周会", "周会", today()),
+            case("Monday.com migration", "Monday.com migration", today()),
+            // 标题里的星期缩写
+            case("Sun Valley trip", "Sun Valley trip", today()),
+            case("Sat prep class", "Sat prep class", today()),
+            case("Mon Cheri chocolates", "Mon Cheri chocolates", today()),
+            case("pack weekend bag", "pack weekend bag", today()),
+            case(
+                "Sun Valley trip on Friday",
+                "Sun Valley trip",
+                date(2026, 3, 13),
+            ),
+        ];
+
+        for case in cases {
+            let expected = QuickAdd {
+                title: case.title.to_string(),
+                start_date: case.start,
+                end_date: case.end,
+                start_time: case.start_time,
+                end_time: case.end_time,
+                recurrence: case.recurrence,
+            };
+            assert_eq!(
+                parse_quick_add(case.input, today()),
+                Some(expected),
+                "输入：{}",
+                case.input
+            );
+        }
+    }
+
+    #[test]
+    fn needs_a_title() {
+        for input in [
+            "",
+            "  ",
+            "明天",
+            "下午3点",
+            "tomorrow 3pm",
+            "每天",
+            "Mar 12-14",
+        ] {
+            assert_eq!(parse_quick_add(input, today()), None, "输入：{}", input);
+        }
+    }
+
+    #[test]
+    fn parses_chinese_numbers() {
+        assert_eq!(parse_chinese_number("三"), Some(3));
+        assert_eq!(parse_chinese_number("十"), Some(10));
+        assert_eq!(parse_chinese_number("十二"), Some(12));
+        assert_eq!(parse_chinese_number("二十"), Some(20));
+        assert_eq!(parse_chinese_number("三十一"), Some(31));
+        assert_eq!(parse_chinese_number("两"), Some(2));
+        assert_eq!(parse_chinese_number("一二"), None);
+        assert_eq!(parse_chinese_number(""), None);
+    }
+
+    #[test]
+    fn lists_occurrences() {
+        assert_eq!(
+            Recurrence::Weekdays.occurrences(date(2026, 3, 13), 3),
+            vec![date(2026, 3, 13), date(2026, 3, 16), date(2026, 3, 17)]
+        );
+        assert_eq!(
+            Recurrence::Monthly(31).occurrences(date(2026, 3, 1), 3),
+            vec![date(2026, 3, 31), date(2026, 5, 31), date(2026, 7, 31)]
+        );
+        assert_eq!(
+            Recurrence::Weekly(Weekday::Mon).occurrences(date(2026, 3, 11), 2),
+            vec![date(2026, 3, 16), date(2026, 3, 23)]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! 键盘快捷键：按键到命令的对应关系，以及命令面板和快捷键说明中列出的命令
+
+use crate::types::ViewMode;
+
+/// 可以通过快捷键或命令面板执行的操作
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    Today,
+    Prev,
+    Next,
+    View(ViewMode),
+    /// 在聚焦的日期（没有时为当前日期）新建事件
+    NewEvent,
+    /// 编辑聚焦的事件
+    EditEvent,
+    /// 删除聚焦的事件
+    DeleteEvent,
+    Search,
+    OpenPalette,
+    ShowShortcuts,
+    Undo,
+    Redo,
+    Refresh,
+    OpenTrash,
+    OpenSettings,
+}
+
+/// 命令面板和快捷键说明中的顺序
+pub const ALL_COMMANDS: [Command; 20] = [
+    Command::Today,
+    Command::Prev,
+    Command::Next,
+    Command::View(ViewMode::Year),
+    Command::View(ViewMode::Month),
+    Command::View(ViewMode::Week),
+    Command::View(ViewMode::Day),
+    Command::View(ViewMode::Agenda),
+    Command::View(ViewMode::Timeline),
+    Command::NewEvent,
+    Command::EditEvent,
+    Command::DeleteEvent,
+    Command::Search,
+    Command::OpenPalette,
+    Command::ShowShortcuts,
+    Command::Undo,
+    Command::Redo,
+    Command::Refresh,
+    Command::OpenTrash,
+    Command::OpenSettings,
+];
+
+impl Command {
+    pub fn label(&self) -> String {
+        match self {
+            Command::Today => "回到今天".to_string(),
+            Command::Prev => "上一段".to_string(),
+            Command::Next => "下一段".to_string(),
+            Command::View(view) => format!("切换到{}视图", view.as_str()),
+            Command::NewEvent => "新建事件".to_string(),
+            Command::EditEvent => "编辑选中的事件".to_string(),
+            Command::DeleteEvent => "删除选中的事件".to_string(),
+            Command::Search => "搜索".to_string(),
+            Command::OpenPalette => "命令面板".to_string(),
+            Command::ShowShortcuts => "快捷键说明".to_string(),
+            Command::Undo => "撤销".to_string(),
+            Command::Redo => "重做".to_string(),
+            Command::Refresh => "重新同步".to_string(),
+            Command::OpenTrash => "打开回收站".to_string(),
+            Command::OpenSettings => "打开设置".to_string(),
+        }
+    }
+
+    /// 显示用的按键，没有快捷键时为空
+    pub fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Command::Today => &["T"],
+            Command::Prev => &["K", "←"],
+            Command::Next => &["J", "→"],
+            Command::View(ViewMode::Year) => &["Y"],
+            Command::View(ViewMode::Month) => &["M"],
+            Command::View(ViewMode::Week) => &["W"],
+            Command::View(ViewMode::Day) => &["D"],
+            Command::View(ViewMode::Agenda) => &["A"],
+            Command::View(ViewMode::Timeline) => &["L"],
+            Command::NewEvent => &["N"],
+            Command::EditEvent => &["E", "Enter"],
+            Command::DeleteEvent => &["Delete"],
+            Command::Search => &["/", "Ctrl+K"],
+            Command::OpenPalette => &["Ctrl+P"],
+            Command::ShowShortcuts => &["?"],
+            Command::Undo => &["Ctrl+Z"],
+            Command::Redo => &["Ctrl+Shift+Z"],
+            Command::Refresh => &[],
+            Command::OpenTrash => &[],
+            Command::OpenSettings => &[],
+        }
+    }
+
+    /// 需要先选中一个事件
+    pub fn needs_event(&self) -> bool {
+        matches!(self, Command::EditEvent | Command::DeleteEvent)
+    }
+}
+
+/// 一次按键；`ctrl` 在 macOS 上也包括 Command 键
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyPress {
+    pub key: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+/// 按键对应的命令；方向键在日期格子上移动焦点，由调用方先行处理
+pub fn command_for_key(press: &KeyPress) -> Option<Command> {
+    if press.alt {
+        return None;
+    }
+    if press.ctrl {
+        return match press.key.to_ascii_lowercase().as_str() {
+            "k" => Some(Command::Search),
+            "p" => Some(Command::OpenPalette),
+            "z" if press.shift => Some(Command::Redo),
+            "z" => Some(Command::Undo),
+            "y" => Some(Command::Redo),
+            _ => None,
+        };
+    }
+    // “?” 本身需要按住 Shift，所以只看字符
+    match press.key.as_str() {
+        "?" => return Some(Command::ShowShortcuts),
+        "/" => return Some(Command::Search),
+        "ArrowLeft" => return Some(Command::Prev),
+        "ArrowRight" => return Some(Command::Next),
+        "Enter" => return Some(Command::EditEvent),
+        "Delete" => return Some(Command::DeleteEvent),
+        _ => {}
+    }
+    if press.shift {
+        return None;
+    }
+    match press.key.to_ascii_lowercase().as_str() {
+        "t" => Some(Command::Today),
+        "k" => Some(Command::Prev),
+        "j" => Some(Command::Next),
+        "y" => Some(Command::View(ViewMode::Year)),
+        "m" => Some(Command::View(ViewMode::Month)),
+        "w" => Some(Command::View(ViewMode::Week)),
+        "d" => Some(Command::View(ViewMode::Day)),
+        "a" => Some(Command::View(ViewMode::Agenda)),
+        "l" => Some(Command::View(ViewMode::Timeline)),
+        "n" => Some(Command::NewEvent),
+        "e" => Some(Command::EditEvent),
+        _ => None,
+    }
+}
+
+/// 方向键在日期格子间移动的天数
+pub fn focus_step(key: &str) -> Option<i64> {
+    match key {
+        "ArrowLeft" => Some(-1),
+        "ArrowRight" => Some(1),
+        "ArrowUp" => Some(-7),
+        "ArrowDown" => Some(7),
+        _ => None,
+    }
+}
+
+/// 命令面板的筛选：标题包含输入的每个词，或按键与输入相同
+pub fn filter_commands(query: &str) -> Vec<Command> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    ALL_COMMANDS
+        .iter()
+        .copied()
+        .filter(|command| {
+            let label = command.label().to_lowercase();
+            terms.iter().all(|term| {
+                label.contains(term.as_str())
+                    || command
+                        .keys()
+                        .iter()
+                        .any(|key| key.eq_ignore_ascii_case(term))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: &str) -> KeyPress {
+        KeyPress {
+            key: key.to_string(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    fn ctrl(key: &str, shift: bool) -> KeyPress {
+        KeyPress {
+            key: key.to_string(),
+            ctrl: true,
+            shift,
+            alt: false,
+        }
+    }
+
+    #[test]
+    fn maps_keys_to_commands() {
+        assert_eq!(command_for_key(&press("t")), Some(Command::Today));
+        assert_eq!(command_for_key(&press("j")), Some(Command::Next));
+        assert_eq!(command_for_key(&press("k")), Some(Command::Prev));
+        assert_eq!(command_for_key(&press("ArrowLeft")), Some(Command::Prev));
+        assert_eq!(
+            command_for_key(&press("W")),
+            Some(Command::View(ViewMode::Week))
+        );
+        assert_eq!(command_for_key(&press("n")), Some(Command::NewEvent));
+        assert_eq!(
+            command_for_key(&press("Delete")),
+            Some(Command::DeleteEvent)
+        );
+        assert_eq!(command_for_key(&press("/")), Some(Command::Search));
+        assert_eq!(
+            command_for_key(&KeyPress {
+                shift: true,
+                ..press("?")
+            }),
+            Some(Command::ShowShortcuts)
+        );
+        assert_eq!(command_for_key(&press("x")), None);
+        // 退格键太容易误按，不用来删除
+        assert_eq!(command_for_key(&press("Backspace")), None);
+    }
+
+    #[test]
+    fn maps_modifier_keys() {
+        assert_eq!(command_for_key(&ctrl("k", false)), Some(Command::Search));
+        assert_eq!(command_for_key(&ctrl("z", false)), Some(Command::Undo));
+        assert_eq!(command_for_key(&ctrl("Z", true)), Some(Command::Redo));
+        // Ctrl+T 等浏览器快捷键不拦截
+        assert_eq!(command_for_key(&ctrl("t", false)), None);
+        assert_eq!(
+            command_for_key(&KeyPress {
+                alt: true,
+                ..press("t")
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn every_key_hint_is_handled() {
+        for command in ALL_COMMANDS {
+            for key in command.keys() {
+                let handled = match *key {
+                    "←" => command_for_key(&press("ArrowLeft")),
+                    "→" => command_for_key(&press("ArrowRight")),
+                    "Ctrl+Shift+Z" => command_for_key(&ctrl("z", true)),
+                    key => match key.strip_prefix("Ctrl+") {
+                        Some(key) => command_for_key(&ctrl(key, false)),
+                        None => command_for_key(&press(key)),
+                    },
+                };
+                assert_eq!(handled, Some(command), "按键：{}", key);
+            }
+        }
+    }
+
+    #[test]
+    fn filters_commands() {
+        assert_eq!(filter_commands("回收站"), vec![Command::OpenTrash]);
+        assert_eq!(filter_commands("t"), vec![Command::Today]);
+        assert_eq!(filter_commands("").len(), ALL_COMMANDS.len());
+        assert!(filter_commands("不存在").is_empty());
+    }
+}